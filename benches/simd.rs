@@ -1,5 +1,8 @@
 use core::ops::Add;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{
+	black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId,
+	Criterion,
+};
 use optimath::Vector;
 use rand::{thread_rng, Rng};
 
@@ -151,5 +154,34 @@ pub fn create(c: &mut Criterion) {
 	});
 }
 
-criterion_group!(sse3, add, mul, create);
+// the fused version never writes the difference to memory
+#[allow(clippy::op_ref)]
+fn fused_size<const S: usize>(group: &mut BenchmarkGroup<WallTime>) {
+	let mut rng = thread_rng();
+	let a: Vector<f32, S> = rng.gen();
+	let b: Vector<f32, S> = rng.gen();
+
+	group.bench_function(BenchmarkId::new("fused", S), |bench| {
+		bench.iter(|| black_box((a.lazy() - &b).norm_squared()))
+	});
+	group.bench_function(BenchmarkId::new("eager", S), |bench| {
+		bench.iter(|| {
+			let difference = &a - &b;
+			black_box(difference.dot(&difference))
+		})
+	});
+}
+
+pub fn fused(c: &mut Criterion) {
+	let mut group = c.benchmark_group("squared distance");
+	group.warm_up_time(core::time::Duration::from_millis(200));
+	group.measurement_time(core::time::Duration::from_secs(2));
+	group.sample_size(250);
+
+	fused_size::<1_000>(&mut group);
+	fused_size::<10_000>(&mut group);
+	fused_size::<100_000>(&mut group);
+}
+
+criterion_group!(sse3, add, mul, create, fused);
 criterion_main!(sse3);
@@ -3,7 +3,7 @@
 
 use crate::{
 	types::{Matrix, Vector},
-	view::{TransposedMatrixView, VectorView},
+	view::{TransposedMatrixView, VectorRef, VectorView},
 };
 
 impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
@@ -50,6 +50,14 @@ where
 	pub fn dot(self, other: &'b Vector<T, M>) -> T { (self * other).into_iter().sum() }
 }
 
+impl<'a, 'b, T: 'a + 'b, const M: usize> VectorRef<'a, T, M>
+where
+	&'a T: core::ops::Mul<&'b T, Output = T>,
+	T: core::iter::Sum,
+{
+	pub fn dot(self, other: &'b Vector<T, M>) -> T { (self * other).into_iter().sum() }
+}
+
 impl<'a, 'b, T: 'a + 'b, const M: usize> Vector<T, M>
 where
 	&'a T: core::ops::Mul<&'b T, Output = T>,
@@ -0,0 +1,236 @@
+//! comparing floats and everything built out of them with some tolerance.
+//!
+//! Vectors compare element by element, so a Matrix is approximately equal if all of its elements
+//! are. the assert macros report the first element that is off:
+//! ```
+//! use optimath::{assert_approx_eq, assert_relative_eq, Vector};
+//! let a: Vector<f32, 3> = Vector::from_array([0.1, 0.2, 0.3]);
+//! let b = Vector::from_array([0.1 + 1e-8, 0.2, 0.3]);
+//! assert_approx_eq!(a, b);
+//! assert_approx_eq!(a, b, epsilon = 1e-6);
+//! assert_relative_eq!(a, b, epsilon = 0., max_relative = 1e-6);
+//! ```
+use crate::{num::Float, types::Vector};
+use core::fmt::Debug;
+
+pub trait ApproxEq {
+	type Epsilon: Copy;
+	/// the type of the innermost elements, the ones that actually get compared
+	type Element: ApproxEq<Epsilon = Self::Epsilon> + Debug;
+	/// the number of elements
+	const LEN: usize;
+
+	fn default_epsilon() -> Self::Epsilon;
+	fn default_max_relative() -> Self::Epsilon;
+	fn default_max_ulps() -> u32;
+
+	/// |self - other| <= epsilon
+	fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+	/// like abs_diff_eq for values close to zero, further away the allowed difference is
+	/// max_relative times the larger of the two
+	fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon)
+		-> bool;
+
+	/// like abs_diff_eq for values close to zero, otherwise the two may be at most max_ulps
+	/// representable floats apart
+	fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+	/// the index (in memory order) and the elements of the first pair for which eq is false
+	fn first_mismatch<'a>(
+		&'a self,
+		other: &'a Self,
+		eq: &mut dyn FnMut(&Self::Element, &Self::Element) -> bool,
+	) -> Option<(usize, &'a Self::Element, &'a Self::Element)>;
+}
+
+macro_rules! float_approx {
+	( $t:ident, $bits:ident ) => {
+		impl ApproxEq for $t {
+			type Epsilon = $t;
+			type Element = $t;
+			const LEN: usize = 1;
+
+			fn default_epsilon() -> $t { $t::EPSILON }
+			fn default_max_relative() -> $t { $t::EPSILON }
+			fn default_max_ulps() -> u32 { 4 }
+
+			fn abs_diff_eq(&self, other: &$t, epsilon: $t) -> bool {
+				// exact comparison first so infinities are equal to themselves
+				self == other || Float::abs(self - other) <= epsilon
+			}
+
+			fn relative_eq(&self, other: &$t, epsilon: $t, max_relative: $t) -> bool {
+				if self.abs_diff_eq(other, epsilon) {
+					return true;
+				}
+				if !self.is_finite() || !other.is_finite() {
+					return false;
+				}
+				let largest = Float::abs(*self).max(Float::abs(*other));
+				Float::abs(self - other) <= largest * max_relative
+			}
+
+			fn ulps_eq(&self, other: &$t, epsilon: $t, max_ulps: u32) -> bool {
+				if self.abs_diff_eq(other, epsilon) {
+					return true;
+				}
+				let signs_differ = self.is_sign_negative() != other.is_sign_negative();
+				if self.is_nan() || other.is_nan() || signs_differ {
+					return false;
+				}
+				// for floats of the same sign the bit patterns are ordered like the values
+				let (a, b) = (self.to_bits(), other.to_bits());
+				let distance = if a > b { a - b } else { b - a };
+				distance <= max_ulps as $bits
+			}
+
+			fn first_mismatch<'a>(
+				&'a self,
+				other: &'a $t,
+				eq: &mut dyn FnMut(&$t, &$t) -> bool,
+			) -> Option<(usize, &'a $t, &'a $t)> {
+				if eq(self, other) {
+					None
+				} else {
+					Some((0, self, other))
+				}
+			}
+		}
+	};
+}
+
+float_approx!(f32, u32);
+float_approx!(f64, u64);
+
+impl<T: ApproxEq, const N: usize> ApproxEq for Vector<T, N> {
+	type Epsilon = T::Epsilon;
+	type Element = T::Element;
+	const LEN: usize = N * T::LEN;
+
+	fn default_epsilon() -> T::Epsilon { T::default_epsilon() }
+	fn default_max_relative() -> T::Epsilon { T::default_max_relative() }
+	fn default_max_ulps() -> u32 { T::default_max_ulps() }
+
+	fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+		self.inner.iter().zip(other).all(|(a, b)| a.abs_diff_eq(b, epsilon))
+	}
+
+	fn relative_eq(&self, other: &Self, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+		self.inner
+			.iter()
+			.zip(other)
+			.all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+	}
+
+	fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+		self.inner.iter().zip(other).all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+	}
+
+	fn first_mismatch<'a>(
+		&'a self,
+		other: &'a Self,
+		eq: &mut dyn FnMut(&T::Element, &T::Element) -> bool,
+	) -> Option<(usize, &'a T::Element, &'a T::Element)> {
+		for (i, (a, b)) in self.inner.iter().zip(other).enumerate() {
+			if let Some((j, a, b)) = a.first_mismatch(b, eq) {
+				return Some((i * T::LEN + j, a, b));
+			}
+		}
+		None
+	}
+}
+
+// used by the assert macros
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_with<T: ApproxEq>(
+	left: &T,
+	right: &T,
+	name: &str,
+	mut eq: impl FnMut(&T::Element, &T::Element) -> bool,
+) {
+	if let Some((index, l, r)) = left.first_mismatch(right, &mut eq) {
+		panic!("{} failed at index {}: {:?} != {:?}", name, index, l, r);
+	}
+}
+
+// the default (epsilon, max_relative) of the type of value
+#[doc(hidden)]
+pub fn defaults<T: ApproxEq>(_value: &T) -> (T::Epsilon, T::Epsilon) {
+	(T::default_epsilon(), T::default_max_relative())
+}
+
+/// asserts that abs_diff_eq holds, with the default epsilon or a given one
+#[macro_export]
+macro_rules! assert_approx_eq {
+	( $left:expr, $right:expr $(,)? ) => {
+		$crate::__assert_approx_with(&$left, &$right, "assert_approx_eq", |a, b| {
+			$crate::ApproxEq::abs_diff_eq(a, b, $crate::__approx_defaults(a).0)
+		})
+	};
+	( $left:expr, $right:expr, epsilon = $epsilon:expr $(,)? ) => {
+		$crate::__assert_approx_with(&$left, &$right, "assert_approx_eq", |a, b| {
+			$crate::ApproxEq::abs_diff_eq(a, b, $epsilon)
+		})
+	};
+}
+
+/// asserts that relative_eq holds, with the default tolerances or given ones
+#[macro_export]
+macro_rules! assert_relative_eq {
+	( $left:expr, $right:expr $(,)? ) => {
+		$crate::__assert_approx_with(&$left, &$right, "assert_relative_eq", |a, b| {
+			let (epsilon, max_relative) = $crate::__approx_defaults(a);
+			$crate::ApproxEq::relative_eq(a, b, epsilon, max_relative)
+		})
+	};
+	( $left:expr, $right:expr, epsilon = $epsilon:expr, max_relative = $max:expr $(,)? ) => {
+		$crate::__assert_approx_with(&$left, &$right, "assert_relative_eq", |a, b| {
+			$crate::ApproxEq::relative_eq(a, b, $epsilon, $max)
+		})
+	};
+}
+
+#[test]
+fn floats() {
+	assert!(1f32.abs_diff_eq(&1.1, 0.2));
+	assert!(!1f32.abs_diff_eq(&1.1, 0.05));
+	assert!(f64::INFINITY.abs_diff_eq(&f64::INFINITY, 0.));
+	assert!(!f64::NAN.relative_eq(&f64::NAN, 1., 1.));
+
+	assert!(1000f64.relative_eq(&1001., 0., 1e-3));
+	assert!(!1000f64.relative_eq(&1002., 0., 1e-3));
+
+	let next = f32::from_bits(1f32.to_bits() + 3);
+	assert!(1f32.ulps_eq(&next, 0., 4));
+	assert!(!1f32.ulps_eq(&next, 0., 2));
+	assert!(!1f32.ulps_eq(&-1., 0., u32::MAX));
+	assert!(0f32.ulps_eq(&-0., 0., 0));
+}
+
+#[test]
+fn vectors() {
+	use crate::Matrix;
+	let a: Matrix<f64, 2, 2> = Matrix::from_fn(|row, column| (row + 2 * column) as f64 / 10.);
+	let mut b = a;
+	b[1][0] += 1e-12;
+	assert_ne!(a, b);
+	assert!(a.abs_diff_eq(&b, 1e-11));
+	assert!(a.relative_eq(&b, 0., 1e-10));
+	assert!(!a.ulps_eq(&b, 0., 4));
+	assert_approx_eq!(a, b, epsilon = 1e-11);
+	assert_relative_eq!(a, b, epsilon = 0., max_relative = 1e-10);
+	assert_approx_eq!(a, a);
+	assert_relative_eq!(a, a);
+
+	b[1][1] = 7.;
+	let (index, left, right) = a.first_mismatch(&b, &mut |x, y| x.abs_diff_eq(y, 1e-11)).unwrap();
+	assert_eq!((index, *left, *right), (3, 0.3, 7.));
+}
+
+#[test]
+#[should_panic(expected = "assert_approx_eq failed at index 2: 3.0 != 3.5")]
+fn assert_message() {
+	assert_approx_eq!(Vector::from_array([1., 2., 3.]), Vector::from_array([1., 2., 3.5]));
+}
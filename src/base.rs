@@ -80,8 +80,33 @@ maths!(&'a Vector<T, N>, T; const N: usize);
 use crate::VectorView;
 maths!(VectorView<'a, T, N, M>, T; const N: usize, const M: usize);
 
+use crate::VectorRef;
+maths!(VectorRef<'a, T, N>, T; const N: usize);
+
 assign_maths!(Vector<T, N>, T; const N: usize);
 
+// VectorMut has no owned inner array, so only the generic half of impl_assign_op applies
+macro_rules! impl_view_assign_op {
+	( $op:tt, $fn:ident ) => {
+		impl<'a, 'b, T, B: 'b, const N: usize> $op<B> for crate::VectorMut<'a, T, N>
+		where
+			T: $op<&'b T> + 'b,
+			B: ConstIndex<&'b T, N> + Copy + Clone,
+		{
+			fn $fn(&mut self, other: B) {
+				for (i, s) in self.inner.iter_mut().enumerate() {
+					$op::$fn(s, other.i(i));
+				}
+			}
+		}
+	};
+}
+
+impl_view_assign_op!(AddAssign, add_assign);
+impl_view_assign_op!(SubAssign, sub_assign);
+impl_view_assign_op!(MulAssign, mul_assign);
+impl_view_assign_op!(DivAssign, div_assign);
+
 #[cfg(test)]
 pub(crate) const TESTLEN: usize = 777usize;
 
@@ -113,6 +138,27 @@ fn operations() {
 	}
 }
 
+#[test]
+fn array_conversions() {
+	use crate::Matrix;
+	let a = Vector::from([1, 2, 3]);
+	assert_eq!(a.into_array(), [1, 2, 3]);
+	let arr: [i32; 3] = a.into();
+	assert_eq!(arr, [1, 2, 3]);
+	assert_eq!(a.iter().sum::<i32>(), 6);
+	assert_eq!(AsRef::<[i32]>::as_ref(&a), &[1, 2, 3]);
+
+	let m = Matrix::from([[1, 2], [3, 4], [5, 6]]);
+	assert_eq!(m[2], Vector::from([5, 6]));
+	let nested: [[i32; 2]; 3] = m.into();
+	assert_eq!(nested, [[1, 2], [3, 4], [5, 6]]);
+
+	const C: Vector<f32, 2> = Vector::from_array([0.5, 1.]);
+	let mut c = C;
+	c.as_mut_slice()[0] = 2.;
+	assert_eq!(&c[..], &[2., 1.]);
+}
+
 #[test]
 fn assignment_operations() {
 	use rand::{thread_rng, Rng};
@@ -0,0 +1,232 @@
+//! complex numbers as an element type.
+//!
+//! all operators exist by value and by reference, so Vectors and Matrices of Complex numbers get
+//! the same element-wise operations as real ones.
+use crate::{
+	num::{Float, MulAdd, One, Zero},
+	types::{Matrix, Stupidity, Vector},
+};
+use core::{
+	iter::Sum,
+	ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// re + im * i, laid out like [re, im]
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Complex<T> {
+	pub re: T,
+	pub im: T,
+}
+
+impl<T> Complex<T> {
+	pub const fn new(re: T, im: T) -> Self { Complex { re, im } }
+}
+
+impl<T: Zero + One> Complex<T> {
+	/// the imaginary unit
+	pub fn i() -> Self { Complex::new(T::zero(), T::one()) }
+}
+
+impl<T: Copy + Neg<Output = T>> Complex<T> {
+	/// the complex conjugate re - im * i
+	pub fn conj(&self) -> Self { Complex::new(self.re, -self.im) }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Complex<T> {
+	/// the squared absolute value, cheaper than norm as there is no square root
+	pub fn norm_sqr(&self) -> T { self.re * self.re + self.im * self.im }
+}
+
+impl<T: Float> Complex<T> {
+	/// the absolute value
+	pub fn norm(&self) -> T { self.norm_sqr().sqrt() }
+
+	/// the angle to the positive real axis in radians, in [-π, π]
+	pub fn arg(&self) -> T { self.im.atan2(self.re) }
+
+	pub fn from_polar(r: T, theta: T) -> Self { Complex::new(r * theta.cos(), r * theta.sin()) }
+
+	/// (norm, arg)
+	pub fn to_polar(&self) -> (T, T) { (self.norm(), self.arg()) }
+}
+
+impl<T: Copy + Add<Output = T>> Add for Complex<T> {
+	type Output = Complex<T>;
+	fn add(self, other: Self) -> Self { Complex::new(self.re + other.re, self.im + other.im) }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub for Complex<T> {
+	type Output = Complex<T>;
+	fn sub(self, other: Self) -> Self { Complex::new(self.re - other.re, self.im - other.im) }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for Complex<T> {
+	type Output = Complex<T>;
+	fn mul(self, other: Self) -> Self {
+		let (a, b, c, d) = (self.re, self.im, other.re, other.im);
+		Complex::new(a * c - b * d, a * d + b * c)
+	}
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Div
+	for Complex<T>
+{
+	type Output = Complex<T>;
+	fn div(self, other: Self) -> Self {
+		let (a, b, c, d) = (self.re, self.im, other.re, other.im);
+		let denominator = c * c + d * d;
+		Complex::new((a * c + b * d) / denominator, (b * c - a * d) / denominator)
+	}
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+	type Output = Complex<T>;
+	fn neg(self) -> Self { Complex::new(-self.re, -self.im) }
+}
+
+impl<T: Copy + Neg<Output = T>> Neg for &Complex<T> {
+	type Output = Complex<T>;
+	fn neg(self) -> Complex<T> { -*self }
+}
+
+/// scaling by a real number
+impl<T: Copy + Mul<Output = T>> Mul<T> for Complex<T> {
+	type Output = Complex<T>;
+	fn mul(self, other: T) -> Self { Complex::new(self.re * other, self.im * other) }
+}
+
+/// scaling by a real number
+impl<T: Copy + Div<Output = T>> Div<T> for Complex<T> {
+	type Output = Complex<T>;
+	fn div(self, other: T) -> Self { Complex::new(self.re / other, self.im / other) }
+}
+
+// the by-reference and assigning versions all forward to the by-value ones
+macro_rules! forward_ref {
+	( $op:tt, $fn:ident, $assign_op:tt, $assign_fn:ident ) => {
+		impl<'a, T: Copy> $op<&'a Complex<T>> for Complex<T>
+		where
+			Complex<T>: $op<Output = Complex<T>>,
+		{
+			type Output = Complex<T>;
+			fn $fn(self, other: &'a Complex<T>) -> Complex<T> { $op::$fn(self, *other) }
+		}
+
+		impl<'a, T: Copy> $op<Complex<T>> for &'a Complex<T>
+		where
+			Complex<T>: $op<Output = Complex<T>>,
+		{
+			type Output = Complex<T>;
+			fn $fn(self, other: Complex<T>) -> Complex<T> { $op::$fn(*self, other) }
+		}
+
+		impl<'a, 'b, T: Copy> $op<&'b Complex<T>> for &'a Complex<T>
+		where
+			Complex<T>: $op<Output = Complex<T>>,
+		{
+			type Output = Complex<T>;
+			fn $fn(self, other: &'b Complex<T>) -> Complex<T> { $op::$fn(*self, *other) }
+		}
+
+		impl<T: Copy> $assign_op for Complex<T>
+		where
+			Complex<T>: $op<Output = Complex<T>>,
+		{
+			fn $assign_fn(&mut self, other: Complex<T>) { *self = $op::$fn(*self, other) }
+		}
+
+		impl<'a, T: Copy> $assign_op<&'a Complex<T>> for Complex<T>
+		where
+			Complex<T>: $op<Output = Complex<T>>,
+		{
+			fn $assign_fn(&mut self, other: &'a Complex<T>) { *self = $op::$fn(*self, *other) }
+		}
+	};
+}
+
+forward_ref!(Add, add, AddAssign, add_assign);
+forward_ref!(Sub, sub, SubAssign, sub_assign);
+forward_ref!(Mul, mul, MulAssign, mul_assign);
+forward_ref!(Div, div, DivAssign, div_assign);
+
+impl<T: Zero> Zero for Complex<T> {
+	fn zero() -> Self { Complex::new(T::zero(), T::zero()) }
+}
+
+impl<T: Zero + One> One for Complex<T> {
+	fn one() -> Self { Complex::new(T::one(), T::zero()) }
+}
+
+impl<T: Copy + Zero + Add<Output = T>> Sum for Complex<T> {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Complex::zero(), |a, b| a + b) }
+}
+
+impl<'a, T: Copy + Zero + Add<Output = T>> Sum<&'a Complex<T>> for Complex<T> {
+	fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+		iter.fold(Complex::zero(), |a, b| a + b)
+	}
+}
+
+/// not fused, there is no single instruction for complex numbers
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> MulAdd for Complex<T> {
+	fn mul_add(self, a: Self, b: Self) -> Self { self * a + b }
+}
+
+impl<T, const N: usize> Vector<Complex<T>, N>
+where
+	T: Copy + Zero + Neg<Output = T> + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+	/// the dot product that conjugates self first, so x.vdot(&x) is the squared norm of x.
+	///
+	/// [dot](#method.dot) does not conjugate.
+	pub fn vdot(&self, other: &Self) -> Complex<T> {
+		self.inner.iter().zip(other).map(|(s, o)| s.conj() * *o).sum()
+	}
+
+	/// conjugates every element
+	pub fn conj(&self) -> Self { Vector::build_with_fn(|i| self[i].conj()) }
+}
+
+impl<T: Copy + Neg<Output = T>, const M: usize, const N: usize> Matrix<Complex<T>, M, N> {
+	/// the conjugate (hermitian) transpose
+	pub fn adjoint(&self) -> Matrix<Complex<T>, N, M> {
+		Vector::build_with_fn(|column| Vector::build_with_fn(|row| self[row][column].conj()))
+	}
+}
+
+#[test]
+// &a - &b is the by-reference impl that Vector operations use
+#[allow(clippy::op_ref)]
+fn complex() {
+	let a = Complex::new(1., 2.);
+	let b = Complex::new(3., -1.);
+	assert_eq!(a + b, Complex::new(4., 1.));
+	assert_eq!(&a - &b, Complex::new(-2., 3.));
+	assert_eq!(a * b, Complex::new(5., 5.));
+	assert_eq!((a * b) / b, a);
+	assert_eq!(-a, Complex::new(-1., -2.));
+	assert_eq!(a * 2., Complex::new(2., 4.));
+	let i: Complex<f64> = Complex::i();
+	assert_eq!(i * i, Complex::new(-1., 0.));
+	assert_eq!(a * a.conj(), Complex::new(a.norm_sqr(), 0.));
+
+	let polar = Complex::from_polar(2., core::f64::consts::FRAC_PI_2);
+	assert!((polar - Complex::new(0., 2.)).norm() < 1e-12);
+	assert!((polar.arg() - core::f64::consts::FRAC_PI_2).abs() < 1e-12);
+	assert_eq!(Complex::new(3., 4.).norm(), 5.);
+
+	let x: Vector<Complex<f64>, 2> = Vector::from_array([a, b]);
+	let y: Vector<Complex<f64>, 2> = Vector::from_array([b, i]);
+	assert_eq!(&x + &y, Vector::from_array([a + b, b + i]));
+	assert_eq!(x.vdot(&x), Complex::new(15., 0.));
+	assert_eq!(x.vdot(&y), a.conj() * b + b.conj() * i);
+	assert_eq!(x.dot(&y), a * b + b * i);
+
+	let m: Matrix<Complex<i32>, 2, 3> = Matrix::from_fn(|row, column| {
+		Complex::new(row as i32, column as i32)
+	});
+	let adjoint = m.adjoint();
+	assert_eq!(adjoint[1][2], Complex::new(1, -2));
+	assert_eq!(adjoint.adjoint(), m);
+}
@@ -0,0 +1,132 @@
+//! commonly used Vectors and Matrices, all built on top of [Stupidity::build_with_fn]
+use crate::{
+	num::{One, Zero},
+	types::{Matrix, Stupidity, Vector},
+};
+use core::ops::{Add, Div, Mul, Sub};
+
+impl<T: Zero, const N: usize> Vector<T, N> {
+	pub fn zeros() -> Self { Zero::zero() }
+}
+
+impl<T: One, const N: usize> Vector<T, N> {
+	pub fn ones() -> Self { One::one() }
+}
+
+impl<T: Clone, const N: usize> Vector<T, N> {
+	/// every element is a copy of value
+	pub fn splat(value: T) -> Self { Self::build_with_fn(|_| value.clone()) }
+}
+
+impl<T: Zero + One, const N: usize> Vector<T, N> {
+	/// the standard basis vector along dimension i
+	pub fn unit(i: usize) -> Self {
+		assert!(i < N, "unit vector along dimension {} of a {} element Vector", i, N);
+		Self::build_with_fn(|j| if i == j { T::one() } else { T::zero() })
+	}
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Vector<T, N> {
+	/// start, start + step, start + step + step, ...
+	pub fn arange(start: T, step: T) -> Self {
+		let mut next = start;
+		Self::build_with_fn(|_| {
+			let current = next;
+			next = next + step;
+			current
+		})
+	}
+}
+
+impl<T, const N: usize> Vector<T, N>
+where
+	T: Copy + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+	/// N evenly spaced values from start to end, both inclusive
+	pub fn linspace(start: T, end: T) -> Self {
+		// N - 1 as a T, without requiring a conversion from usize
+		let intervals = (1..N).fold(T::zero(), |acc, _| acc + T::one());
+		let mut position = T::zero();
+		Self::build_with_fn(|i| {
+			let value = if i == 0 {
+				start
+			} else if i + 1 == N {
+				end
+			} else {
+				start + (end - start) * position / intervals
+			};
+			position = position + T::one();
+			value
+		})
+	}
+}
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
+	/// f is called with (row, column)
+	pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
+		Self::build_with_fn(|column| Vector::build_with_fn(|row| f(row, column)))
+	}
+
+	/// Matrices store their columns, so this does not need to move anything around
+	pub fn from_columns(columns: [Vector<T, M>; N]) -> Self { Vector::from_array(columns) }
+}
+
+impl<T: Clone, const M: usize, const N: usize> Matrix<T, M, N> {
+	pub fn from_rows(rows: [Vector<T, N>; M]) -> Self {
+		Self::from_fn(|row, column| rows[row][column].clone())
+	}
+}
+
+impl<T: Zero + One, const N: usize> Matrix<T, N, N> {
+	pub fn identity() -> Self {
+		Self::from_fn(|row, column| if row == column { T::one() } else { T::zero() })
+	}
+}
+
+impl<T: Zero + Clone, const N: usize> Matrix<T, N, N> {
+	pub fn from_diagonal(diagonal: &Vector<T, N>) -> Self {
+		Self::from_fn(|row, column| {
+			if row == column {
+				diagonal[row].clone()
+			} else {
+				T::zero()
+			}
+		})
+	}
+}
+
+#[test]
+fn constructors() {
+	assert_eq!(Vector::<i32, 3>::zeros(), Vector::from([0, 0, 0]));
+	assert_eq!(Vector::<i32, 3>::ones(), Vector::from([1, 1, 1]));
+	assert_eq!(Vector::<i32, 3>::splat(7), Vector::from([7, 7, 7]));
+	assert_eq!(Vector::<i32, 3>::unit(1), Vector::from([0, 1, 0]));
+	assert_eq!(Vector::<i32, 4>::arange(2, 3), Vector::from([2, 5, 8, 11]));
+	assert_eq!(
+		Vector::<f32, 5>::linspace(-1., 1.),
+		Vector::from([-1., -0.5, 0., 0.5, 1.])
+	);
+	assert_eq!(Vector::<f32, 1>::linspace(3., 5.), Vector::from([3.]));
+
+	let m: Matrix<i32, 2, 3> = Matrix::from_fn(|row, column| (10 * row + column) as i32);
+	assert_eq!(m, Matrix::from([[0, 10], [1, 11], [2, 12]]));
+	assert_eq!(
+		Matrix::from_rows([Vector::from([0, 1, 2]), Vector::from([10, 11, 12])]),
+		m
+	);
+	assert_eq!(
+		Matrix::from_columns([
+			Vector::from([0, 10]),
+			Vector::from([1, 11]),
+			Vector::from([2, 12])
+		]),
+		m
+	);
+
+	let identity = Matrix::<i32, 3, 3>::identity();
+	assert_eq!(identity.matrix_multiply(&m.transpose().materialize()), m.transpose().materialize());
+	assert_eq!(
+		Matrix::from_diagonal(&Vector::from([1, 2])),
+		Matrix::from([[1, 0], [0, 2]])
+	);
+}
@@ -35,6 +35,19 @@ unsafe impl<'a, T, const N: usize> ConstIndex<&'a mut T, N> for &'a mut Vector<T
 	fn i(self, index: usize) -> &'a mut T { &mut self.inner[index] }
 }
 
+use crate::{VectorMut, VectorRef};
+unsafe impl<'a, T, const N: usize> ConstIndex<&'a T, N> for VectorRef<'a, T, N> {
+	fn i(self, index: usize) -> &'a T { &self.inner[index] }
+}
+
+unsafe impl<'a, 'b, T, const N: usize> ConstIndex<&'b T, N> for &'b VectorMut<'a, T, N> {
+	fn i(self, index: usize) -> &'b T { &self.inner[index] }
+}
+
+unsafe impl<'a, 'b, T, const N: usize> ConstIndex<&'b mut T, N> for &'b mut VectorMut<'a, T, N> {
+	fn i(self, index: usize) -> &'b mut T { &mut self.inner[index] }
+}
+
 use crate::VectorView;
 unsafe impl<'a, T, const M: usize, const N: usize> ConstIndex<&'a T, M>
 	for VectorView<'a, T, M, N>
@@ -0,0 +1,192 @@
+//! convolution and correlation, for fir filters and small image kernels.
+//!
+//! output sizes like N + K - 1 can not be calculated in const generics yet, so the caller names
+//! the output size and it gets checked against the mode at runtime.
+use crate::{
+	num::Zero,
+	types::{Matrix, Stupidity, Vector},
+};
+use core::ops::{Add, Mul};
+
+/// which part of the full convolution is returned, same as in numpy
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConvolutionMode {
+	/// every overlap, N + K - 1 elements
+	Full,
+	/// centered on the input, N elements
+	Same,
+	/// only where the kernel lies fully inside the input, N - K + 1 elements
+	Valid,
+}
+
+impl ConvolutionMode {
+	/// (output length, offset into the full convolution)
+	fn layout(self, n: usize, k: usize) -> (usize, usize) {
+		match self {
+			ConvolutionMode::Full => (n + k - 1, 0),
+			ConvolutionMode::Same => (n, (k - 1) / 2),
+			ConvolutionMode::Valid => (n.saturating_sub(k - 1), k - 1),
+		}
+	}
+}
+
+/// how values outside of the matrix are made up
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Border {
+	/// everything outside is zero
+	Zero,
+	/// repeats the edge, aaa|abcd|ddd
+	Clamp,
+	/// continues from the other side, bcd|abcd|abc
+	Wrap,
+	/// mirrors including the edge, cba|abcd|dcb
+	Reflect,
+}
+
+impl Border {
+	/// maps a possibly out of bounds index into 0..n, None means zero
+	fn resolve(self, i: isize, n: usize) -> Option<usize> {
+		let n = n as isize;
+		if (0..n).contains(&i) {
+			return Some(i as usize);
+		}
+		match self {
+			Border::Zero => None,
+			Border::Clamp => Some(i.clamp(0, n - 1) as usize),
+			Border::Wrap => Some(i.rem_euclid(n) as usize),
+			Border::Reflect => {
+				let m = i.rem_euclid(2 * n);
+				Some(if m < n { m } else { 2 * n - 1 - m } as usize)
+			},
+		}
+	}
+}
+
+impl<T: Copy + Zero + Add<Output = T> + Mul<Output = T>, const N: usize> Vector<T, N> {
+	/// the discrete convolution of self and kernel.
+	///
+	/// O has to be the output size of the mode, panics otherwise or if K is 0.
+	pub fn convolve<const K: usize, const O: usize>(
+		&self,
+		kernel: &Vector<T, K>,
+		mode: ConvolutionMode,
+	) -> Vector<T, O> {
+		assert!(K > 0, "convolution with an empty kernel");
+		let (len, offset) = mode.layout(N, K);
+		assert_eq!(O, len, "{:?} convolution of {} and {} elements", mode, N, K);
+		Vector::build_with_fn(|o| {
+			let i = o + offset;
+			// all k with 0 <= i - k < N
+			let first = (i + 1).saturating_sub(N);
+			let last = K.min(i + 1);
+			(first..last).fold(T::zero(), |acc, k| acc + self[i - k] * kernel[k])
+		})
+	}
+
+	/// the cross-correlation, i.e. the convolution with the reversed kernel.
+	///
+	/// the modes and sizes are the same as for [convolve](#method.convolve).
+	pub fn correlate<const K: usize, const O: usize>(
+		&self,
+		kernel: &Vector<T, K>,
+		mode: ConvolutionMode,
+	) -> Vector<T, O> {
+		let reversed: Vector<T, K> = Vector::build_with_fn(|k| kernel[K - 1 - k]);
+		self.convolve(&reversed, mode)
+	}
+}
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N>
+where
+	T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+	/// 2d convolution with a (KM x KN) kernel, the output has the same size as self.
+	///
+	/// the kernel is centered on each element, (KM - 1) / 2 rows and (KN - 1) / 2 columns in.
+	pub fn convolve2d<const KM: usize, const KN: usize>(
+		&self,
+		kernel: &Matrix<T, KM, KN>,
+		border: Border,
+	) -> Matrix<T, M, N> {
+		let (center_row, center_column) = ((KM as isize - 1) / 2, (KN as isize - 1) / 2);
+		Matrix::from_fn(|row, column| {
+			let mut acc = T::zero();
+			for (j, kernel_column) in kernel.inner.iter().enumerate() {
+				let source_column = column as isize - j as isize + center_column;
+				let source_column = match border.resolve(source_column, N) {
+					Some(c) => &self[c],
+					None => continue,
+				};
+				for (i, k) in kernel_column.inner.iter().enumerate() {
+					let source_row = row as isize - i as isize + center_row;
+					if let Some(r) = border.resolve(source_row, M) {
+						acc = acc + source_column[r] * *k;
+					}
+				}
+			}
+			acc
+		})
+	}
+}
+
+#[test]
+fn convolve_1d() {
+	use ConvolutionMode::*;
+	let x = Vector::from_array([1, 2, 3]);
+	let h = Vector::from_array([0, 1, 2]);
+	// numpy.convolve
+	assert_eq!(x.convolve(&h, Full), Vector::from_array([0, 1, 4, 7, 6]));
+	assert_eq!(x.convolve(&h, Same), Vector::from_array([1, 4, 7]));
+	assert_eq!(x.convolve(&h, Valid), Vector::from_array([4]));
+	assert_eq!(
+		x.convolve(&Vector::from_array([1, 1]), Same),
+		Vector::from_array([1, 3, 5])
+	);
+	// numpy.correlate
+	assert_eq!(x.correlate(&h, Full), Vector::from_array([2, 5, 8, 3, 0]));
+	assert_eq!(x.correlate(&h, Valid), Vector::from_array([8]));
+
+	let long: Vector<i32, 6> = Vector::arange(1, 1);
+	let moving_sum: Vector<i32, 4> = long.convolve(&Vector::from_array([1, 1, 1]), Valid);
+	assert_eq!(moving_sum, Vector::from_array([6, 9, 12, 15]));
+}
+
+#[test]
+#[should_panic]
+fn convolve_wrong_size() {
+	let x = Vector::from_array([1, 2, 3]);
+	let _: Vector<i32, 4> = x.convolve(&Vector::from_array([1]), ConvolutionMode::Full);
+}
+
+#[test]
+fn convolve_2d() {
+	let m: Matrix<i32, 3, 3> = Matrix::from_fn(|row, column| (3 * row + column) as i32);
+	let mut identity: Matrix<i32, 3, 3> = Matrix::zero();
+	identity[1][1] = 1;
+	for border in &[Border::Zero, Border::Clamp, Border::Wrap, Border::Reflect] {
+		assert_eq!(m.convolve2d(&identity, *border), m);
+	}
+
+	// picks the element one column to the left
+	let mut shift: Matrix<i32, 1, 3> = Matrix::zero();
+	shift[2][0] = 1;
+	let row = |b| {
+		let out = m.convolve2d(&shift, b);
+		[out[0][0], out[1][0], out[2][0]]
+	};
+	assert_eq!(row(Border::Zero), [0, 0, 1]);
+	assert_eq!(row(Border::Clamp), [0, 0, 1]);
+	assert_eq!(row(Border::Wrap), [2, 0, 1]);
+	assert_eq!(row(Border::Reflect), [0, 0, 1]);
+
+	let ones: Matrix<i32, 3, 3> = Matrix::ones();
+	let box_sum = m.convolve2d(&ones, Border::Zero);
+	assert_eq!(box_sum[1][1], (0..9).sum::<i32>());
+	// 0 1
+	// 3 4
+	assert_eq!(box_sum[0][0], 8);
+	// 0 0 1
+	// 0 0 1
+	// 3 3 4
+	assert_eq!(m.convolve2d(&ones, Border::Clamp)[0][0], 12);
+}
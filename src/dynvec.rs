@@ -1,4 +1,448 @@
-struct DynVec<'a, T> {
-	inner: &'a [T],
-	len: usize,
+//! heap allocated vectors and matrices, for when the size is only known at runtime
+//!
+//! dimensions are checked at runtime. the operator impls panic on a mismatch, just like indexing
+//! out of bounds does, the try_* methods return a [DimensionMismatch] instead.
+use crate::types::{Matrix, Stupidity, Vector};
+use alloc::vec::Vec;
+use core::{
+	convert::TryFrom,
+	iter::{FromIterator, Sum},
+	ops::*,
+};
+
+/// the dimensions of two operands did not fit together.
+///
+/// only reports the first dimension that did not match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionMismatch {
+	pub expected: usize,
+	pub found: usize,
+}
+
+impl DimensionMismatch {
+	fn check(expected: usize, found: usize) -> Result<(), Self> {
+		if expected == found {
+			Ok(())
+		} else {
+			Err(DimensionMismatch { expected, found })
+		}
+	}
+}
+
+impl core::fmt::Display for DimensionMismatch {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"dimension mismatch: expected {} but found {}",
+			self.expected, self.found
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DimensionMismatch {}
+
+/// a vector of runtime-known length, supports the same element-wise operations as [Vector]
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct DynVector<T> {
+	pub(crate) inner: Vec<T>,
+}
+
+impl<T> DynVector<T> {
+	pub fn build_with_fn<F: FnMut(usize) -> T>(len: usize, f: F) -> Self {
+		(0..len).map(f).collect()
+	}
+
+	pub fn len(&self) -> usize { self.inner.len() }
+	pub fn is_empty(&self) -> bool { self.inner.is_empty() }
+
+	pub fn as_slice(&self) -> &[T] { &self.inner }
+	pub fn as_mut_slice(&mut self) -> &mut [T] { &mut self.inner }
+	pub fn into_vec(self) -> Vec<T> { self.inner }
+
+	pub fn iter(&self) -> core::slice::Iter<'_, T> { self.inner.iter() }
+}
+
+impl<T> From<Vec<T>> for DynVector<T> {
+	fn from(inner: Vec<T>) -> Self { DynVector { inner } }
+}
+
+impl<T, const N: usize> From<Vector<T, N>> for DynVector<T> {
+	fn from(vector: Vector<T, N>) -> Self { vector.into_iter().collect() }
+}
+
+impl<T, const N: usize> TryFrom<DynVector<T>> for Vector<T, N> {
+	type Error = DimensionMismatch;
+	fn try_from(vector: DynVector<T>) -> Result<Self, DimensionMismatch> {
+		DimensionMismatch::check(N, vector.len())?;
+		Ok(vector.inner.into_iter().collect())
+	}
+}
+
+impl<T> Index<usize> for DynVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &T { &self.inner[index] }
+}
+
+impl<T> IndexMut<usize> for DynVector<T> {
+	fn index_mut(&mut self, index: usize) -> &mut T { &mut self.inner[index] }
+}
+
+impl<T> FromIterator<T> for DynVector<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		DynVector {
+			inner: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<T> IntoIterator for DynVector<T> {
+	type Item = T;
+	type IntoIter = alloc::vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter { self.inner.into_iter() }
+}
+
+impl<'a, T> IntoIterator for &'a DynVector<T> {
+	type Item = &'a T;
+	type IntoIter = core::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter { self.inner.iter() }
+}
+
+impl<'a, 'b, T: 'a + 'b> DynVector<T>
+where
+	&'a T: Mul<&'b T, Output = T>,
+	T: Sum,
+{
+	pub fn try_dot(&'a self, other: &'b DynVector<T>) -> Result<T, DimensionMismatch> {
+		Ok(self.try_mul(other)?.into_iter().sum())
+	}
+
+	/// panics if the lengths differ
+	pub fn dot(&'a self, other: &'b DynVector<T>) -> T {
+		self.try_dot(other).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+/// a matrix of runtime-known size.
+///
+/// just like [Matrix] it is stored column by column, so it converts to and from [Matrix] without
+/// shuffling elements around.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct DynMatrix<T> {
+	pub(crate) rows: usize,
+	pub(crate) columns: usize,
+	pub(crate) inner: Vec<T>,
+}
+
+impl<T> DynMatrix<T> {
+	pub fn build_with_fn<F: FnMut(usize, usize) -> T>(
+		rows: usize,
+		columns: usize,
+		mut f: F,
+	) -> Self {
+		let inner = (0..columns)
+			.flat_map(|column| (0..rows).map(move |row| (row, column)))
+			.map(|(row, column)| f(row, column))
+			.collect();
+		DynMatrix {
+			rows,
+			columns,
+			inner,
+		}
+	}
+
+	/// takes the elements column by column
+	pub fn from_vec(rows: usize, columns: usize, inner: Vec<T>) -> Result<Self, DimensionMismatch> {
+		DimensionMismatch::check(rows * columns, inner.len())?;
+		Ok(DynMatrix {
+			rows,
+			columns,
+			inner,
+		})
+	}
+
+	pub fn rows(&self) -> usize { self.rows }
+	pub fn columns(&self) -> usize { self.columns }
+
+	pub fn column(&self, column: usize) -> &[T] {
+		&self.inner[column * self.rows..(column + 1) * self.rows]
+	}
+
+	pub fn column_mut(&mut self, column: usize) -> &mut [T] {
+		&mut self.inner[column * self.rows..(column + 1) * self.rows]
+	}
+
+	/// all elements, column by column
+	pub fn as_slice(&self) -> &[T] { &self.inner }
+	pub fn into_vec(self) -> Vec<T> { self.inner }
+
+	fn check_shape(&self, other: &DynMatrix<T>) -> Result<(), DimensionMismatch> {
+		DimensionMismatch::check(self.rows, other.rows)?;
+		DimensionMismatch::check(self.columns, other.columns)
+	}
+}
+
+impl<T: Clone> DynMatrix<T> {
+	/// unlike [Matrix::transpose] this copies the elements into a new matrix
+	pub fn transpose(&self) -> DynMatrix<T> {
+		DynMatrix::build_with_fn(self.columns, self.rows, |row, column| {
+			self[(column, row)].clone()
+		})
+	}
+}
+
+impl<'a, 'b, T: 'a + 'b> DynMatrix<T>
+where
+	&'a T: Mul<&'b T, Output = T>,
+	T: Sum,
+{
+	pub fn try_matrix_multiply(
+		&'a self,
+		other: &'b DynMatrix<T>,
+	) -> Result<DynMatrix<T>, DimensionMismatch> {
+		DimensionMismatch::check(self.columns, other.rows)?;
+		Ok(DynMatrix::build_with_fn(
+			self.rows,
+			other.columns,
+			|row, column| {
+				let row = (0..self.columns).map(|i| &self[(row, i)]);
+				row.zip(other.column(column)).map(|(s, o)| s * o).sum()
+			},
+		))
+	}
+
+	/// panics if the number of columns of self differs from the number of rows of other
+	pub fn matrix_multiply(&'a self, other: &'b DynMatrix<T>) -> DynMatrix<T> {
+		self.try_matrix_multiply(other)
+			.unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+/// indexed by (row, column)
+impl<T> Index<(usize, usize)> for DynMatrix<T> {
+	type Output = T;
+	fn index(&self, (row, column): (usize, usize)) -> &T {
+		assert!(row < self.rows);
+		&self.inner[column * self.rows + row]
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for DynMatrix<T> {
+	fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut T {
+		assert!(row < self.rows);
+		&mut self.inner[column * self.rows + row]
+	}
+}
+
+impl<T, const M: usize, const N: usize> From<Matrix<T, M, N>> for DynMatrix<T> {
+	fn from(matrix: Matrix<T, M, N>) -> Self {
+		DynMatrix {
+			rows: M,
+			columns: N,
+			inner: matrix.into_iter().flatten().collect(),
+		}
+	}
+}
+
+impl<T, const M: usize, const N: usize> TryFrom<DynMatrix<T>> for Matrix<T, M, N> {
+	type Error = DimensionMismatch;
+	fn try_from(matrix: DynMatrix<T>) -> Result<Self, DimensionMismatch> {
+		DimensionMismatch::check(M, matrix.rows)?;
+		DimensionMismatch::check(N, matrix.columns)?;
+		let mut iter = matrix.inner.into_iter();
+		Ok(Matrix::build_with_fn(|_| (&mut iter).take(M).collect()))
+	}
+}
+
+// element-wise operations, mirroring the macros in base.rs
+macro_rules! dyn_op {
+	( $op:tt, $fn:ident, $try_fn:ident ) => {
+		impl<T> DynVector<T> {
+			pub fn $try_fn<'a, 'b>(
+				&'a self,
+				other: &'b DynVector<T>,
+			) -> Result<DynVector<T>, DimensionMismatch>
+			where
+				&'a T: $op<&'b T, Output = T>,
+			{
+				DimensionMismatch::check(self.len(), other.len())?;
+				Ok(self.iter().zip(other).map(|(s, o)| $op::$fn(s, o)).collect())
+			}
+		}
+
+		impl<'a, 'b, T> $op<&'b DynVector<T>> for &'a DynVector<T>
+		where
+			&'a T: $op<&'b T, Output = T>,
+		{
+			type Output = DynVector<T>;
+			fn $fn(self, other: &'b DynVector<T>) -> DynVector<T> {
+				self.$try_fn(other).unwrap_or_else(|e| panic!("{}", e))
+			}
+		}
+
+		impl<T> DynMatrix<T> {
+			pub fn $try_fn<'a, 'b>(
+				&'a self,
+				other: &'b DynMatrix<T>,
+			) -> Result<DynMatrix<T>, DimensionMismatch>
+			where
+				&'a T: $op<&'b T, Output = T>,
+			{
+				self.check_shape(other)?;
+				Ok(DynMatrix {
+					rows: self.rows,
+					columns: self.columns,
+					inner: self
+						.inner
+						.iter()
+						.zip(&other.inner)
+						.map(|(s, o)| $op::$fn(s, o))
+						.collect(),
+				})
+			}
+		}
+
+		impl<'a, 'b, T> $op<&'b DynMatrix<T>> for &'a DynMatrix<T>
+		where
+			&'a T: $op<&'b T, Output = T>,
+		{
+			type Output = DynMatrix<T>;
+			fn $fn(self, other: &'b DynMatrix<T>) -> DynMatrix<T> {
+				self.$try_fn(other).unwrap_or_else(|e| panic!("{}", e))
+			}
+		}
+	};
+}
+
+dyn_op!(Add, add, try_add);
+dyn_op!(Sub, sub, try_sub);
+dyn_op!(Mul, mul, try_mul);
+dyn_op!(Div, div, try_div);
+
+macro_rules! dyn_assign_op {
+	( $op:tt, $fn:ident, $try_fn:ident ) => {
+		impl<T> DynVector<T> {
+			pub fn $try_fn<'a>(&mut self, other: &'a DynVector<T>) -> Result<(), DimensionMismatch>
+			where
+				T: $op<&'a T>,
+			{
+				DimensionMismatch::check(self.len(), other.len())?;
+				for (s, o) in self.inner.iter_mut().zip(other) {
+					$op::$fn(s, o);
+				}
+				Ok(())
+			}
+		}
+
+		impl<'a, T> $op<&'a DynVector<T>> for DynVector<T>
+		where
+			T: $op<&'a T>,
+		{
+			fn $fn(&mut self, other: &'a DynVector<T>) {
+				self.$try_fn(other).unwrap_or_else(|e| panic!("{}", e))
+			}
+		}
+
+		impl<T> DynMatrix<T> {
+			pub fn $try_fn<'a>(&mut self, other: &'a DynMatrix<T>) -> Result<(), DimensionMismatch>
+			where
+				T: $op<&'a T>,
+			{
+				self.check_shape(other)?;
+				for (s, o) in self.inner.iter_mut().zip(&other.inner) {
+					$op::$fn(s, o);
+				}
+				Ok(())
+			}
+		}
+
+		impl<'a, T> $op<&'a DynMatrix<T>> for DynMatrix<T>
+		where
+			T: $op<&'a T>,
+		{
+			fn $fn(&mut self, other: &'a DynMatrix<T>) {
+				self.$try_fn(other).unwrap_or_else(|e| panic!("{}", e))
+			}
+		}
+	};
+}
+
+dyn_assign_op!(AddAssign, add_assign, try_add_assign);
+dyn_assign_op!(SubAssign, sub_assign, try_sub_assign);
+dyn_assign_op!(MulAssign, mul_assign, try_mul_assign);
+dyn_assign_op!(DivAssign, div_assign, try_div_assign);
+
+#[test]
+fn dyn_operations() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	let a: Vector<f32, 77> = rng.gen();
+	let b: Vector<f32, 77> = rng.gen();
+	let da = DynVector::from(a);
+	let db = DynVector::from(b);
+
+	let add = &da + &db;
+	assert_eq!(Vector::try_from(add), Ok(&a + &b));
+	let mut sub = da.clone();
+	sub -= &db;
+	assert_eq!(Vector::try_from(sub), Ok(&a - &b));
+	assert_eq!(da.dot(&db), a.dot(&b));
+
+	let short = DynVector::build_with_fn(3, |i| i as f32);
+	let err = DimensionMismatch {
+		expected: 77,
+		found: 3,
+	};
+	assert_eq!(da.try_add(&short), Err(err));
+	assert_eq!(da.clone().try_mul_assign(&short), Err(err));
+	assert_eq!(da.try_dot(&short), Err(err));
+	assert_eq!(Vector::<f32, 3>::try_from(da), Err(DimensionMismatch {
+		expected: 3,
+		found: 77,
+	}));
+}
+
+#[test]
+fn dyn_matrix_multiply() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	let a: Matrix<i32, 2, 3> = rng.gen::<Matrix<i8, 2, 3>>()
+		.into_iter()
+		.map(|c| c.into_iter().map(i32::from).collect())
+		.collect();
+	let b: Matrix<i32, 3, 4> = rng.gen::<Matrix<i8, 3, 4>>()
+		.into_iter()
+		.map(|c| c.into_iter().map(i32::from).collect())
+		.collect();
+	let c = a.matrix_multiply(&b);
+
+	let da = DynMatrix::from(a);
+	let db = DynMatrix::from(b);
+	let dc = da.matrix_multiply(&db);
+	assert_eq!((dc.rows(), dc.columns()), (2, 4));
+	assert_eq!(Matrix::try_from(dc), Ok(c));
+
+	assert_eq!(Matrix::try_from(da.transpose()), Ok(a.transpose().materialize()));
+	assert_eq!(db.try_matrix_multiply(&da), Err(DimensionMismatch {
+		expected: 4,
+		found: 2,
+	}));
+	assert_eq!(da.try_add(&db).map(|_| ()), Err(DimensionMismatch {
+		expected: 2,
+		found: 3,
+	}));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn boxed_error() {
+	use std::{boxed::Box, error::Error, string::ToString};
+	fn fixed(v: DynVector<i32>) -> Result<Vector<i32, 3>, Box<dyn Error>> {
+		Ok(Vector::try_from(v)?)
+	}
+	let error = fixed((0..2).collect()).unwrap_err();
+	assert_eq!(error.to_string(), "dimension mismatch: expected 3 but found 2");
+	assert_eq!(fixed((0..3).collect()).unwrap(), Vector::from_array([0, 1, 2]));
 }
@@ -0,0 +1,333 @@
+//! fast fourier transforms on split real/imaginary Vectors, without allocating.
+//!
+//! the twiddle factors for a size N are stored in [Twiddles], which can be built in a const
+//! context, so the tables end up in the binary instead of being calculated on the device:
+//! ```
+//! use optimath::{Twiddles, Vector};
+//! const TWIDDLES: Twiddles<f32, 8> = Twiddles::<f32, 8>::new();
+//!
+//! let mut re: Vector<f32, 8> = Vector::from_array([1., 0., 0., 0., 0., 0., 0., 0.]);
+//! let mut im: Vector<f32, 8> = Vector::from_array([0.; 8]);
+//! TWIDDLES.fft(&mut re, &mut im);
+//! assert_eq!(re, Vector::from_array([1.; 8]));
+//! ```
+use crate::{
+	num::{Float, Zero},
+	types::Vector,
+};
+
+/// exp(-2πik/n) as (re, im), usable in const context.
+///
+/// the angle is reduced to [0, π/4] with exact integer arithmetic first, where a short taylor
+/// series is accurate to the last bit.
+const fn unit_root(k: usize, n: usize) -> (f64, f64) {
+	let k = k % n;
+	// which quarter of the circle and the position inside of it, in units of 1/(4n) turns
+	let quadrant = 4 * k / n;
+	let rest = 4 * k - quadrant * n;
+	let (sin, cos) = if 2 * rest <= n {
+		sin_cos_small(core::f64::consts::FRAC_PI_2 * rest as f64 / n as f64)
+	} else {
+		// sin(x) = cos(π/2 - x) and the other way around
+		let (s, c) = sin_cos_small(core::f64::consts::FRAC_PI_2 * (n - rest) as f64 / n as f64);
+		(c, s)
+	};
+	// rotate by the quarters, then flip the sign for the negative exponent
+	let (cos, sin) = match quadrant {
+		0 => (cos, sin),
+		1 => (-sin, cos),
+		2 => (-cos, -sin),
+		_ => (sin, -cos),
+	};
+	(cos, -sin)
+}
+
+/// (sin, cos) of x for |x| <= π/4
+const fn sin_cos_small(x: f64) -> (f64, f64) {
+	let x2 = x * x;
+	// horner scheme of the taylor series, starting at the x^22 and x^23 terms
+	let (mut sin, mut cos) = (1., 1.);
+	let mut i = 11;
+	while i > 0 {
+		let n = (2 * i) as f64;
+		sin = 1. - x2 / (n * (n + 1.)) * sin;
+		cos = 1. - x2 / ((n - 1.) * n) * cos;
+		i -= 1;
+	}
+	(x * sin, cos)
+}
+
+/// the twiddle factors exp(-2πik/N) for a power of two fft of size N.
+///
+/// build them once, ideally in a const, and reuse them for every transform.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Twiddles<T, const N: usize> {
+	pub(crate) re: Vector<T, N>,
+	pub(crate) im: Vector<T, N>,
+}
+
+macro_rules! const_twiddles {
+	( $t:ty ) => {
+		impl<const N: usize> Twiddles<$t, N> {
+			pub const fn new() -> Self {
+				let (mut re, mut im) = ([0.; N], [0.; N]);
+				let mut k = 0;
+				while k < N {
+					let (c, s) = unit_root(k, N);
+					re[k] = c as $t;
+					im[k] = s as $t;
+					k += 1;
+				}
+				Twiddles {
+					re: Vector::from_array(re),
+					im: Vector::from_array(im),
+				}
+			}
+		}
+
+		impl<const N: usize> Default for Twiddles<$t, N> {
+			fn default() -> Self { Self::new() }
+		}
+	};
+}
+
+const_twiddles!(f32);
+const_twiddles!(f64);
+
+/// in place radix-2 decimation in time, re.len() has to be a power of two dividing the table size
+fn radix2<T: Float>(re: &mut [T], im: &mut [T], tw_re: &[T], tw_im: &[T]) {
+	let n = re.len();
+	if n < 2 {
+		return;
+	}
+	let stride = tw_re.len() / n;
+
+	// bit reversal permutation
+	let mut j = 0;
+	for i in 1..n {
+		let mut bit = n >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+		if i < j {
+			re.swap(i, j);
+			im.swap(i, j);
+		}
+	}
+
+	let mut len = 2;
+	while len <= n {
+		let half = len / 2;
+		// the twiddles of a size len transform are every (N / len)th entry of the table
+		let step = stride * (n / len);
+		for start in (0..n).step_by(len) {
+			for k in 0..half {
+				let (wr, wi) = (tw_re[k * step], tw_im[k * step]);
+				let (a, b) = (start + k, start + k + half);
+				let tr = re[b] * wr - im[b] * wi;
+				let ti = re[b] * wi + im[b] * wr;
+				re[b] = re[a] - tr;
+				im[b] = im[a] - ti;
+				re[a] = re[a] + tr;
+				im[a] = im[a] + ti;
+			}
+		}
+		len *= 2;
+	}
+}
+
+fn negate<T: Float>(values: &mut [T]) {
+	for v in values {
+		*v = -*v;
+	}
+}
+
+fn scale<T: Float>(values: &mut [T], factor: T) {
+	for v in values {
+		*v = *v * factor;
+	}
+}
+
+impl<T: Float, const N: usize> Twiddles<T, N> {
+	fn assert_power_of_two() {
+		assert!(N.is_power_of_two(), "fft size {} is not a power of two", N)
+	}
+
+	/// forward transform in place, panics if N is not a power of two
+	pub fn fft(&self, re: &mut Vector<T, N>, im: &mut Vector<T, N>) {
+		Self::assert_power_of_two();
+		radix2(&mut re.inner, &mut im.inner, &self.re.inner, &self.im.inner);
+	}
+
+	/// inverse transform in place, including the 1/N scaling
+	pub fn ifft(&self, re: &mut Vector<T, N>, im: &mut Vector<T, N>) {
+		// conj(fft(conj(x))) / N
+		negate(&mut im.inner);
+		self.fft(re, im);
+		negate(&mut im.inner);
+		let factor = T::one() / T::from_f64(N as f64);
+		scale(&mut re.inner, factor);
+		scale(&mut im.inner, factor);
+	}
+
+	/// the transform of real input, only the non-redundant half of the spectrum is returned.
+	///
+	/// runs a single fft of size N / 2. the output size K has to be N / 2 + 1.
+	pub fn rfft<const K: usize>(&self, x: &Vector<T, N>) -> (Vector<T, K>, Vector<T, K>) {
+		Self::assert_power_of_two();
+		assert!(
+			N >= 2 && K == N / 2 + 1,
+			"rfft of {} values has {} outputs, not {}",
+			N,
+			N / 2 + 1,
+			K
+		);
+		let n = N / 2;
+
+		// pack the even values into the real part and the odd values into the imaginary part
+		let (mut z_re, mut z_im) = (x.inner, x.inner);
+		for k in 0..n {
+			z_re[k] = x[2 * k];
+			z_im[k] = x[2 * k + 1];
+		}
+		radix2(&mut z_re[..n], &mut z_im[..n], &self.re.inner, &self.im.inner);
+
+		// and unpack the two half-size spectra
+		let half = T::one() / (T::one() + T::one());
+		let mut re = Vector::zero();
+		let mut im = Vector::zero();
+		for k in 0..K {
+			let (zr, zi) = (z_re[k % n], z_im[k % n]);
+			let (cr, ci) = (z_re[(n - k % n) % n], -z_im[(n - k % n) % n]);
+			// even = (z_k + conj(z_{n-k})) / 2, odd = (z_k - conj(z_{n-k})) / 2i
+			let (er, ei) = ((zr + cr) * half, (zi + ci) * half);
+			let (or, oi) = ((zi - ci) * half, (cr - zr) * half);
+			let (wr, wi) = (self.re[k % N], self.im[k % N]);
+			re[k] = er + or * wr - oi * wi;
+			im[k] = ei + or * wi + oi * wr;
+		}
+		(re, im)
+	}
+
+	/// forward transform of any size L, using power of two transforms of size N (Bluestein's
+	/// algorithm). N has to be at least 2L - 1.
+	pub fn bluestein<const L: usize>(&self, re: &mut Vector<T, L>, im: &mut Vector<T, L>) {
+		Self::assert_power_of_two();
+		assert!(N + 1 >= 2 * L, "bluestein for size {} needs an fft of at least {}", L, 2 * L - 1);
+		// the chirp exp(-πik²/L), using k² mod 2L to keep the angle small
+		let chirp = |k: usize| {
+			let (c, s) = unit_root(k * k % (2 * L), 2 * L);
+			(T::from_f64(c), T::from_f64(s))
+		};
+
+		let (mut a_re, mut a_im) = (Vector::zero(), Vector::zero());
+		for k in 0..L {
+			let (c, s) = chirp(k);
+			a_re[k] = re[k] * c - im[k] * s;
+			a_im[k] = re[k] * s + im[k] * c;
+		}
+		// conj(chirp), wrapped around so the cyclic convolution is the linear one
+		let (mut b_re, mut b_im) = (Vector::zero(), Vector::zero());
+		for k in 0..L {
+			let (c, s) = chirp(k);
+			b_re[k] = c;
+			b_im[k] = -s;
+			if k != 0 {
+				b_re[N - k] = c;
+				b_im[N - k] = -s;
+			}
+		}
+
+		self.fft(&mut a_re, &mut a_im);
+		self.fft(&mut b_re, &mut b_im);
+		for k in 0..N {
+			let r = a_re[k] * b_re[k] - a_im[k] * b_im[k];
+			a_im[k] = a_re[k] * b_im[k] + a_im[k] * b_re[k];
+			a_re[k] = r;
+		}
+		self.ifft(&mut a_re, &mut a_im);
+
+		for k in 0..L {
+			let (c, s) = chirp(k);
+			re[k] = a_re[k] * c - a_im[k] * s;
+			im[k] = a_re[k] * s + a_im[k] * c;
+		}
+	}
+
+	/// inverse of [bluestein](#method.bluestein), including the 1/L scaling
+	pub fn inverse_bluestein<const L: usize>(&self, re: &mut Vector<T, L>, im: &mut Vector<T, L>) {
+		negate(&mut im.inner);
+		self.bluestein(re, im);
+		negate(&mut im.inner);
+		let factor = T::one() / T::from_f64(L as f64);
+		scale(&mut re.inner, factor);
+		scale(&mut im.inner, factor);
+	}
+}
+
+#[cfg(test)]
+fn naive_dft<const N: usize>(
+	re: &Vector<f64, N>,
+	im: &Vector<f64, N>,
+) -> (Vector<f64, N>, Vector<f64, N>) {
+	use crate::types::Stupidity;
+	let (mut out_re, mut out_im) = (Vector::zero(), Vector::zero());
+	for j in 0..N {
+		let angle = |k: usize| -2. * core::f64::consts::PI * (j * k) as f64 / N as f64;
+		let c: Vector<f64, N> = Vector::build_with_fn(|k| angle(k).cos());
+		let s: Vector<f64, N> = Vector::build_with_fn(|k| angle(k).sin());
+		out_re[j] = re.dot(&c) - im.dot(&s);
+		out_im[j] = re.dot(&s) + im.dot(&c);
+	}
+	(out_re, out_im)
+}
+
+#[test]
+fn fft_against_dft() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	const TWIDDLES: Twiddles<f64, 16> = Twiddles::<f64, 16>::new();
+	let x: Vector<f64, 16> = rng.gen();
+	let y: Vector<f64, 16> = rng.gen();
+
+	let (mut re, mut im) = (x, y);
+	TWIDDLES.fft(&mut re, &mut im);
+	let (dft_re, dft_im) = naive_dft(&x, &y);
+	crate::assert_approx_eq!(re, dft_re, epsilon = 1e-9);
+	crate::assert_approx_eq!(im, dft_im, epsilon = 1e-9);
+	TWIDDLES.ifft(&mut re, &mut im);
+	crate::assert_approx_eq!(re, x, epsilon = 1e-9);
+	crate::assert_approx_eq!(im, y, epsilon = 1e-9);
+
+	let (real_re, real_im): (Vector<f64, 9>, Vector<f64, 9>) = TWIDDLES.rfft(&x);
+	let (dft_re, dft_im) = naive_dft(&x, &Vector::zero());
+	for k in 0..9 {
+		assert!((real_re[k] - dft_re[k]).abs() < 1e-9);
+		assert!((real_im[k] - dft_im[k]).abs() < 1e-9);
+	}
+
+	let x: Vector<f64, 7> = rng.gen();
+	let y: Vector<f64, 7> = rng.gen();
+	let (mut re, mut im) = (x, y);
+	TWIDDLES.bluestein(&mut re, &mut im);
+	let (dft_re, dft_im) = naive_dft(&x, &y);
+	crate::assert_approx_eq!(re, dft_re, epsilon = 1e-9);
+	crate::assert_approx_eq!(im, dft_im, epsilon = 1e-9);
+	TWIDDLES.inverse_bluestein(&mut re, &mut im);
+	crate::assert_approx_eq!(re, x, epsilon = 1e-9);
+	crate::assert_approx_eq!(im, y, epsilon = 1e-9);
+}
+
+#[test]
+fn const_twiddles() {
+	let twiddles = Twiddles::<f64, 64>::new();
+	for k in 0..64 {
+		let angle: f64 = -2. * core::f64::consts::PI * k as f64 / 64.;
+		assert!((twiddles.re[k] - angle.cos()).abs() < 1e-15);
+		assert!((twiddles.im[k] - angle.sin()).abs() < 1e-15);
+	}
+	assert_eq!(twiddles.re[16], 0.);
+	assert_eq!(twiddles.im[16], -1.);
+}
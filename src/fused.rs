@@ -0,0 +1,142 @@
+//! fused multiply-add and the blas style kernels built on top of it.
+//!
+//! y.axpy(a, &x) does the same as y += &(a * x) but without the temporary vector, the kernels
+//! that take a beta also scale the target, so no temporary is needed for that either.
+//!
+//! the assigning operators fuse too, if the right hand side is made with [Vector::scaled]:
+//! ```
+//! use optimath::Vector;
+//! let x = Vector::from_array([1., 2., 3.]);
+//! let mut y = Vector::from_array([1., 1., 1.]);
+//! y += x.scaled(2.);
+//! assert_eq!(y, Vector::from_array([3., 5., 7.]));
+//! y -= x.scaled(3.);
+//! assert_eq!(y, Vector::from_array([0., -1., -2.]));
+//! ```
+use crate::{
+	num::MulAdd,
+	types::{Matrix, Stupidity, Vector},
+};
+use core::ops::{AddAssign, Mul, Neg, SubAssign};
+
+impl<T: MulAdd + Copy, const N: usize> Vector<T, N> {
+	/// self * a + b element-wise
+	pub fn mul_add(&self, a: &Self, b: &Self) -> Self {
+		Vector::build_with_fn(|i| self[i].mul_add(a[i], b[i]))
+	}
+
+	/// self = alpha * x + self
+	pub fn axpy(&mut self, alpha: T, x: &Self) {
+		for (s, x) in self.inner.iter_mut().zip(x) {
+			*s = alpha.mul_add(*x, *s);
+		}
+	}
+}
+
+/// alpha * x, only as the right hand side of += and -=, which then fuse it like [Vector::axpy]
+#[derive(Debug)]
+pub struct Scaled<'a, T, const N: usize> {
+	alpha: T,
+	x: &'a Vector<T, N>,
+}
+
+impl<T, const N: usize> Vector<T, N> {
+	/// `y += x.scaled(alpha)` is `y.axpy(alpha, &x)`
+	pub fn scaled(&self, alpha: T) -> Scaled<'_, T, N> { Scaled { alpha, x: self } }
+}
+
+impl<'a, T: MulAdd + Copy, const N: usize> AddAssign<Scaled<'a, T, N>> for Vector<T, N> {
+	fn add_assign(&mut self, other: Scaled<'a, T, N>) { self.axpy(other.alpha, other.x) }
+}
+
+impl<'a, T: MulAdd + Neg<Output = T> + Copy, const N: usize> SubAssign<Scaled<'a, T, N>>
+	for Vector<T, N>
+{
+	fn sub_assign(&mut self, other: Scaled<'a, T, N>) { self.axpy(-other.alpha, other.x) }
+}
+
+impl<T: MulAdd + Mul<Output = T> + Copy, const N: usize> Vector<T, N> {
+	/// self = alpha * x + beta * self
+	pub fn axpby(&mut self, alpha: T, x: &Self, beta: T) {
+		for (s, x) in self.inner.iter_mut().zip(x) {
+			*s = alpha.mul_add(*x, beta * *s);
+		}
+	}
+
+	/// self = alpha * a * x + beta * self, a is (N x K)
+	///
+	/// walks a column by column, as that is how it is laid out in memory
+	pub fn gemv<const K: usize>(
+		&mut self,
+		alpha: T,
+		a: &Matrix<T, N, K>,
+		x: &Vector<T, K>,
+		beta: T,
+	) {
+		for s in self.inner.iter_mut() {
+			*s = beta * *s;
+		}
+		for (column, x) in a.inner.iter().zip(x) {
+			self.axpy(alpha * *x, column);
+		}
+	}
+}
+
+impl<T: MulAdd + Mul<Output = T> + Copy, const M: usize, const O: usize> Matrix<T, M, O> {
+	/// self = alpha * a * b + beta * self, a is (M x N) and b is (N x O)
+	pub fn gemm<const N: usize>(
+		&mut self,
+		alpha: T,
+		a: &Matrix<T, M, N>,
+		b: &Matrix<T, N, O>,
+		beta: T,
+	) {
+		for (column, b) in self.inner.iter_mut().zip(b) {
+			column.gemv(alpha, a, b, beta);
+		}
+	}
+}
+
+#[test]
+fn fused() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+
+	let x: Vector<i64, 5> = Vector::build_with_fn(|_| rng.gen_range(-100, 100));
+	let y: Vector<i64, 5> = Vector::build_with_fn(|_| rng.gen_range(-100, 100));
+	assert_eq!(x.mul_add(&y, &x), &(&x * &y) + &x);
+
+	let mut z = y;
+	z.axpy(3, &x);
+	assert_eq!(z, Vector::build_with_fn(|i| 3 * x[i] + y[i]));
+	let mut fused = y;
+	fused += x.scaled(3);
+	assert_eq!(fused, z);
+	fused -= x.scaled(3);
+	assert_eq!(fused, y);
+	// the plain operators are still there
+	fused += &x;
+	assert_eq!(fused, &y + &x);
+	z = y;
+	z.axpby(3, &x, -2);
+	assert_eq!(z, Vector::build_with_fn(|i| 3 * x[i] - 2 * y[i]));
+
+	let a: Matrix<i64, 3, 4> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let b: Matrix<i64, 4, 2> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let c: Matrix<i64, 3, 2> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let v: Vector<i64, 4> = Vector::build_with_fn(|_| rng.gen_range(-100, 100));
+
+	let mut w = c[0];
+	w.gemv(2, &a, &v, 5);
+	let av = a.matrix_multiply(&v.ascend())[0];
+	assert_eq!(w, Vector::build_with_fn(|i| 2 * av[i] + 5 * c[0][i]));
+
+	let mut d = c;
+	d.gemm(2, &a, &b, 5);
+	let ab = a.matrix_multiply(&b);
+	assert_eq!(d, Matrix::from_fn(|row, column| 2 * ab[column][row] + 5 * c[column][row]));
+
+	// only one rounding, so the error of the square is still visible
+	let e = 1. + f64::EPSILON;
+	assert_eq!(MulAdd::mul_add(e, e, -(e * e)), f64::EPSILON * f64::EPSILON);
+}
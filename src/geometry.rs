@@ -0,0 +1,180 @@
+//! the usual 2d/3d helpers, plus projections and interpolation for any N.
+//!
+//! everything that needs a square root or an angle is only available for [Float] types
+use crate::{
+	num::{clamp_unit, Float},
+	types::{Stupidity, Vector},
+};
+use core::ops::{Add, Mul, Sub};
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vector<T, 3> {
+	pub fn cross(&self, other: &Self) -> Self {
+		let (a, b) = (self, other);
+		Vector::from_array([
+			a[1] * b[2] - a[2] * b[1],
+			a[2] * b[0] - a[0] * b[2],
+			a[0] * b[1] - a[1] * b[0],
+		])
+	}
+
+	/// self · (b × c), the signed volume of the parallelepiped spanned by the three
+	pub fn triple_product(&self, b: &Self, c: &Self) -> T
+	where
+		T: Add<Output = T>,
+	{
+		let bc = b.cross(c);
+		self[0] * bc[0] + self[1] * bc[1] + self[2] * bc[2]
+	}
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vector<T, 2> {
+	/// the z component of the cross product of the two, extended to 3d
+	pub fn perp_dot(&self, other: &Self) -> T { self[0] * other[1] - self[1] * other[0] }
+}
+
+// homogeneous coordinates
+macro_rules! homogeneous {
+	( $n:literal, $m:literal ) => {
+		impl<T> Vector<T, $n> {
+			/// appends w, usually 1 for points and 0 for directions
+			pub fn extend(self, w: T) -> Vector<T, $m> {
+				let mut iter = self.into_iter().chain(core::iter::once(w));
+				Vector::build_with_fn(|_| iter.next().unwrap())
+			}
+		}
+
+		impl<T> Vector<T, $m> {
+			/// drops the last element
+			pub fn truncate(self) -> Vector<T, $n> {
+				let mut iter = self.into_iter();
+				Vector::build_with_fn(|_| iter.next().unwrap())
+			}
+		}
+	};
+}
+
+homogeneous!(2, 3);
+homogeneous!(3, 4);
+
+impl<T: Float, const N: usize> Vector<T, N> {
+	pub(crate) fn fdot(&self, other: &Self) -> T {
+		self.inner
+			.iter()
+			.zip(other)
+			.fold(T::zero(), |acc, (s, o)| s.mul_add(*o, acc))
+	}
+
+	pub fn norm(&self) -> T { self.fdot(self).sqrt() }
+
+	/// self with a length of 1, divides by zero if self is zero
+	pub fn normalize(&self) -> Self {
+		let norm = self.norm();
+		Vector::build_with_fn(|i| self[i] / norm)
+	}
+
+	/// in radians, between 0 and π
+	pub fn angle_between(&self, other: &Self) -> T {
+		clamp_unit(self.fdot(other) / (self.norm() * other.norm())).acos()
+	}
+
+	/// the part of self that points in the direction of other
+	pub fn project_onto(&self, other: &Self) -> Self {
+		let factor = self.fdot(other) / other.fdot(other);
+		Vector::build_with_fn(|i| other[i] * factor)
+	}
+
+	/// the part of self that is orthogonal to other
+	pub fn reject_from(&self, other: &Self) -> Self {
+		let projection = self.project_onto(other);
+		Vector::build_with_fn(|i| self[i] - projection[i])
+	}
+
+	/// mirrors self on the plane with the given normal, normal has to have a length of 1
+	pub fn reflect(&self, normal: &Self) -> Self {
+		let factor = -(self.fdot(normal) + self.fdot(normal));
+		Vector::build_with_fn(|i| factor.mul_add(normal[i], self[i]))
+	}
+
+	/// linear interpolation, t = 0 is self and t = 1 is other
+	pub fn lerp(&self, other: &Self, t: T) -> Self {
+		Vector::build_with_fn(|i| t.mul_add(other[i] - self[i], self[i]))
+	}
+
+	/// spherical interpolation, meant for vectors with a length of 1.
+	///
+	/// falls back to lerp if the two are (almost) parallel as the angle gets unstable there.
+	/// (almost) opposite vectors have no unique great circle between them, the rotation goes
+	/// through the coordinate axis self is least aligned with. with N = 1 there is no way around,
+	/// so that is a lerp as well.
+	pub fn slerp(&self, other: &Self, t: T) -> Self {
+		let theta = self.angle_between(other);
+		let sin = theta.sin();
+		if sin.abs() <= T::EPSILON {
+			let right_angle = T::from_f64(core::f64::consts::FRAC_PI_2);
+			if theta < right_angle || N < 2 {
+				return self.lerp(other, t);
+			}
+			return self.half_turn(t);
+		}
+		let a = ((T::one() - t) * theta).sin() / sin;
+		let b = (t * theta).sin() / sin;
+		Vector::build_with_fn(|i| a.mul_add(self[i], b * other[i]))
+	}
+
+	/// rotates self by t * π towards -self, through some direction orthogonal to self
+	fn half_turn(&self, t: T) -> Self {
+		let mut least_aligned = 0;
+		for i in 1..N {
+			if self[i].abs() < self[least_aligned].abs() {
+				least_aligned = i;
+			}
+		}
+		let axis: Self =
+			Vector::build_with_fn(|i| if i == least_aligned { T::one() } else { T::zero() });
+		let axis = axis.reject_from(self).normalize();
+		let angle = t * T::from_f64(core::f64::consts::PI);
+		let (a, b) = (angle.cos(), angle.sin());
+		Vector::build_with_fn(|i| a.mul_add(self[i], b * axis[i]))
+	}
+}
+
+#[test]
+fn geometry() {
+	let x = Vector::from_array([1., 0., 0.]);
+	let y = Vector::from_array([0., 1., 0.]);
+	let z = Vector::from_array([0., 0., 1.]);
+	assert_eq!(x.cross(&y), z);
+	assert_eq!(y.cross(&x), Vector::from_array([0., 0., -1.]));
+	assert_eq!(x.triple_product(&y, &z), 1.);
+	assert_eq!(Vector::from_array([1, 2]).perp_dot(&Vector::from_array([3, 4])), -2);
+
+	assert_eq!(x.extend(1.).truncate(), x);
+	assert_eq!(Vector::from_array([1, 2]).extend(3), Vector::from_array([1, 2, 3]));
+
+	let v: Vector<f64, 3> = Vector::from_array([3., 4., 0.]);
+	assert_eq!(v.norm(), 5.);
+	assert_eq!(v.project_onto(&x), Vector::from_array([3., 0., 0.]));
+	assert_eq!(v.reject_from(&x), Vector::from_array([0., 4., 0.]));
+	assert_eq!(v.reflect(&y), Vector::from_array([3., -4., 0.]));
+	assert_eq!(v.lerp(&x, 0.5), Vector::from_array([2., 2., 0.]));
+
+	let right_angle = core::f64::consts::FRAC_PI_2;
+	assert!((x.angle_between(&y) - right_angle).abs() < 1e-12);
+	assert_eq!(x.angle_between(&x), 0.);
+
+	let halfway = x.slerp(&y, 0.5);
+	assert!((halfway.norm() - 1.).abs() < 1e-12);
+	assert!((halfway.angle_between(&x) - right_angle / 2.).abs() < 1e-12);
+	assert_eq!(x.slerp(&x, 0.3), x);
+
+	// opposite vectors still stay on the unit sphere
+	let v = Vector::from_array([0.6, 0., 0.8]);
+	let minus_v = Vector::from_array([-0.6, 0., -0.8]);
+	let halfway = v.slerp(&minus_v, 0.5);
+	assert!((halfway.norm() - 1.).abs() < 1e-12);
+	assert!(halfway.fdot(&v).abs() < 1e-12);
+	crate::assert_approx_eq!(v.slerp(&minus_v, 1.), minus_v, epsilon = 1e-12);
+	crate::assert_approx_eq!(v.slerp(&minus_v, 0.), v, epsilon = 1e-12);
+	let one = Vector::from_array([1.]);
+	assert_eq!(one.slerp(&Vector::from_array([-1.]), 0.5), Vector::from_array([0.]));
+}
@@ -31,6 +31,8 @@
 //!     let c: Vector<i32, 2000> = Vector::build_with_fn(|i| i as i32);
 //!     // or using Default
 //!     let d: Vector<i32, 2000> = Default::default();
+//!     // common ones like zeros, ones, unit, linspace and arange have their own constructors
+//!     let g: Vector<i32, 2000> = Vector::zeros();
 //!
 //!     let e = &a + &b;
 //!     let f = &c + &d;
@@ -106,7 +108,7 @@
 //!
 //!
 //! ### 0.X.0
-//! * [ ] interaction with dynamically sized vectors
+//! * [x] interaction with dynamically sized vectors (DynVector/DynMatrix, alloc feature)
 //!     * [ ] widows-function on dynamically sized vectors
 //!
 //! ### 0.X.0
@@ -119,6 +121,7 @@
 //! ### 0.X.0
 //! * [ ] a BLAS compatible interface, including a C-interface. Probably in a different crate based
 //! on this
+//!     * [x] blas style in-place kernels (mul_add, axpy, axpby, gemv, gemm)
 //! * [ ] have 2 additional contributors :) come join the fun and headache about weird compiler bugs
 //! and pointer offset calculations
 //!
@@ -147,6 +150,9 @@
 //! especially for dimensions like matrix multiplication, U x S(3) * S(3) x U = U x U could be a
 //! common case to self multiply a list with unknown number of entries but known number of features
 //! (this is probably also blocked on the same rust bug, but i did not test yet)
+//!
+//! The U x S(F) half of that is available as [Rows] behind the alloc feature, it keeps the unknown
+//! dimension in a Vec of Vectors.
 
 //mod dimensional;
 
@@ -165,18 +171,66 @@ mod advanced;
 mod consts;
 // views on underlying vectors
 mod view;
+// minimal numeric traits
+mod num;
+// zeros, identity and friends
+mod constructors;
+// mul_add, axpy, gemv and gemm
+mod fused;
+// cross products, projections and interpolation
+mod geometry;
+// rotations
+mod quaternion;
+// affine transforms and projections
+mod transform;
+// complex numbers as element type
+mod complex;
+// fourier transforms
+mod fft;
+// fir filters and image kernels
+mod convolution;
+// element-wise comparisons
+mod mask;
+// comparing with tolerances
+mod approx;
+// sorting elements
+mod sort;
+// grids, numpy and latex output
+pub mod pretty;
+// viewing Vectors as bytes
+pub mod pod;
+// matrix market and csv
+pub mod text;
 
 pub mod templatemetamath;
 
 #[cfg(feature = "serde")]
 mod serialize;
 
+// flat, nested and shaped serde representations
+#[cfg(feature = "serde")]
+pub mod repr;
+
 #[cfg(feature = "rand")]
 mod random;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "alloc")]
 mod dynvec;
 
+// lists of unknown length with a known number of features
+#[cfg(feature = "alloc")]
+mod rows;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// numpy files
+#[cfg(feature = "std")]
+pub mod npy;
+
 #[doc(hidden)]
 pub mod benching;
 
@@ -196,8 +250,29 @@ mod avx;
 
 mod layout;
 */
+pub use approx::ApproxEq;
+#[doc(hidden)]
+pub use approx::{assert_with as __assert_approx_with, defaults as __approx_defaults};
+pub use complex::Complex;
 pub use consts::ConstIndex;
+pub use convolution::{Border, ConvolutionMode};
+pub use fft::Twiddles;
+pub use fused::Scaled;
+pub use num::{Float, MulAdd, One, Zero};
+pub use pod::{CastError, Pod};
+pub use quaternion::Quaternion;
+pub use text::TextError;
+pub use transform::Isometry;
 pub use types::{Matrix, Stupidity, Vector};
-pub use view::{TransposedMatrixView, VectorView};
+pub use view::{TransposedMatrixView, VectorMut, VectorRef, VectorView};
+
+#[cfg(feature = "rand")]
+pub use random::{
+	HaarOrthogonal, SymmetricPositiveDefinite, UnitBall, UnitSphere, VectorNormal, VectorUniform,
+};
+#[cfg(feature = "alloc")]
+pub use dynvec::{DimensionMismatch, DynMatrix, DynVector};
+#[cfg(feature = "alloc")]
+pub use rows::Rows;
 // add a type like StaticSizedIterator to make reasoning about dimensions easier/enable
 // optimizations
@@ -0,0 +1,123 @@
+//! element-wise comparisons, boolean masks and selecting through them.
+//!
+//! these are plain loops over compile time sized arrays, which the compiler already turns into
+//! simd compares and blends.
+use crate::types::{Stupidity, Vector};
+
+macro_rules! compare {
+	( $( $name:ident, $op:tt, $doc:literal; )* ) => {
+		impl<T: PartialOrd, const N: usize> Vector<T, N> {
+			$(
+				#[doc = $doc]
+				pub fn $name(&self, other: &Self) -> Vector<bool, N> {
+					Vector::build_with_fn(|i| self[i] $op other[i])
+				}
+			)*
+		}
+	};
+}
+
+compare! {
+	lt, <, "self < other for every element";
+	le, <=, "self <= other for every element";
+	gt, >, "self > other for every element";
+	ge, >=, "self >= other for every element";
+	eq_elem, ==, "self == other for every element, unlike == which compares the whole Vector";
+	ne_elem, !=, "self != other for every element";
+}
+
+impl<const N: usize> Vector<bool, N> {
+	pub fn and(&self, other: &Self) -> Self { Vector::build_with_fn(|i| self[i] & other[i]) }
+	pub fn or(&self, other: &Self) -> Self { Vector::build_with_fn(|i| self[i] | other[i]) }
+	pub fn not(&self) -> Self { Vector::build_with_fn(|i| !self[i]) }
+
+	pub fn count_true(&self) -> usize { self.inner.iter().filter(|b| **b).count() }
+	/// false for N = 0
+	pub fn any(&self) -> bool { self.inner.iter().any(|b| *b) }
+	/// true for N = 0
+	pub fn all(&self) -> bool { self.inner.iter().all(|b| *b) }
+}
+
+impl<T: Clone, const N: usize> Vector<T, N> {
+	/// picks the element of if_true where mask is set and of if_false everywhere else
+	pub fn select(mask: &Vector<bool, N>, if_true: &Self, if_false: &Self) -> Self {
+		Vector::build_with_fn(|i| {
+			if mask[i] {
+				if_true[i].clone()
+			} else {
+				if_false[i].clone()
+			}
+		})
+	}
+}
+
+// for NaN the comparisons are false, so the element of self is kept
+impl<T: PartialOrd + Copy, const N: usize> Vector<T, N> {
+	/// every element limited to [min, max].
+	///
+	/// takes self by value, so it is found before Ord::clamp in method calls
+	pub fn clamp(self, min: T, max: T) -> Self {
+		Vector::build_with_fn(|i| clamp(self[i], min, max))
+	}
+
+	/// every element limited by the matching elements of min and max
+	pub fn clamp_elem(self, min: &Self, max: &Self) -> Self {
+		Vector::build_with_fn(|i| clamp(self[i], min[i], max[i]))
+	}
+
+	/// the smaller of the two for every element
+	pub fn min_elem(&self, other: &Self) -> Self {
+		Vector::build_with_fn(|i| if other[i] < self[i] { other[i] } else { self[i] })
+	}
+
+	/// the larger of the two for every element
+	pub fn max_elem(&self, other: &Self) -> Self {
+		Vector::build_with_fn(|i| if other[i] > self[i] { other[i] } else { self[i] })
+	}
+}
+
+fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> T {
+	if value < min {
+		min
+	} else if value > max {
+		max
+	} else {
+		value
+	}
+}
+
+#[test]
+fn masks() {
+	let a = Vector::from_array([1., 5., 3., f32::NAN]);
+	let b = Vector::from_array([2., 5., 1., 0.]);
+
+	assert_eq!(a.lt(&b), Vector::from_array([true, false, false, false]));
+	assert_eq!(a.le(&b), Vector::from_array([true, true, false, false]));
+	assert_eq!(a.gt(&b), Vector::from_array([false, false, true, false]));
+	assert_eq!(a.ge(&b), Vector::from_array([false, true, true, false]));
+	assert_eq!(a.eq_elem(&b), Vector::from_array([false, true, false, false]));
+	assert_eq!(a.ne_elem(&b), Vector::from_array([true, false, true, true]));
+
+	let mask = a.lt(&b).or(&a.gt(&b));
+	assert_eq!(mask, Vector::from_array([true, false, true, false]));
+	assert_eq!(mask.and(&a.lt(&b)), a.lt(&b));
+	assert_eq!(mask.not().count_true(), 2);
+	assert!(mask.any() && !mask.all());
+
+	let picked = Vector::select(&mask, &a, &b);
+	assert_eq!(picked.as_slice()[..3], [1., 5., 3.]);
+	assert_eq!(picked[3], 0.);
+
+	let c = a.clamp(2., 4.);
+	assert_eq!(c.as_slice()[..3], [2., 4., 3.]);
+	assert!(c[3].is_nan());
+	let c = b.clamp_elem(&Vector::splat(1.5), &Vector::from_array([3., 3., 3., 1.]));
+	assert_eq!(c, Vector::from_array([2., 3., 1.5, 1.5]));
+
+	let x = Vector::from_array([1, 7, -3]);
+	let y = Vector::from_array([4, 2, -3]);
+	assert_eq!(x.min_elem(&y), Vector::from_array([1, 2, -3]));
+	assert_eq!(x.max_elem(&y), Vector::from_array([4, 7, -3]));
+	assert_eq!(x.clamp(0, 5), Vector::from_array([1, 5, 0]));
+	assert_eq!(x.clamp_elem(&y, &Vector::splat(4)), Vector::from_array([4, 4, -3]));
+}
@@ -0,0 +1,353 @@
+//! reading and writing numpy's .npy files.
+//!
+//! a `Vector<Vector<T, M>, N>` is stored column by column, so it is written as an (M, N) array in
+//! fortran order. in general the shape of a file is the nesting of the Vectors from the inside
+//! out. files in C order are transposed while reading, so both
+//! `np.save("m.npy", np.asfortranarray(m))` and plain `np.save("m.npy", m)` load as the same
+//! Matrix.
+//! ```
+//! use optimath::Matrix;
+//! let m: Matrix<f32, 2, 3> = Matrix::from_fn(|row, column| (row * 3 + column) as f32);
+//! let mut file = Vec::new();
+//! m.write_npy(&mut file).unwrap();
+//! assert!(file.starts_with(b"\x93NUMPY\x01\x00"));
+//! assert_eq!(Matrix::<f32, 2, 3>::read_npy(&file[..]).unwrap(), m);
+//! // the element type and the shape are checked
+//! assert!(Matrix::<f64, 2, 3>::read_npy(&file[..]).is_err());
+//! assert!(Matrix::<f32, 3, 2>::read_npy(&file[..]).is_err());
+//! ```
+use crate::{complex::Complex, pod, pod::Pod, types::Vector};
+use std::{
+	fmt,
+	format,
+	io::{self, Read, Write},
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+
+/// the scalar types that numpy knows about
+pub trait Element: Pod + Default {
+	/// the numpy dtype in native byte order, like "<f4"
+	const DESCR: &'static str;
+}
+
+macro_rules! impl_element {
+	( $( $t:ty, $little:literal, $big:literal; )* ) => {
+		$(
+			impl Element for $t {
+				const DESCR: &'static str =
+					if cfg!(target_endian = "little") { $little } else { $big };
+			}
+		)*
+	};
+}
+
+impl_element! {
+	u8, "|u1", "|u1";
+	i8, "|i1", "|i1";
+	u16, "<u2", ">u2";
+	i16, "<i2", ">i2";
+	u32, "<u4", ">u4";
+	i32, "<i4", ">i4";
+	u64, "<u8", ">u8";
+	i64, "<i8", ">i8";
+	f32, "<f4", ">f4";
+	f64, "<f8", ">f8";
+	Complex<f32>, "<c8", ">c8";
+	Complex<f64>, "<c16", ">c16";
+}
+
+/// scalars and (nested) Vectors of them
+pub trait Array: Sized {
+	type Element: Element;
+	/// the length of every level of nesting, outermost first
+	fn storage_shape(shape: &mut Vec<usize>);
+	/// every element in memory order
+	fn for_each(&self, f: &mut dyn FnMut(&Self::Element));
+	/// builds Self from the elements in memory order
+	fn build(f: &mut dyn FnMut() -> Self::Element) -> Self;
+}
+
+impl<E: Element> Array for E {
+	type Element = E;
+	fn storage_shape(_shape: &mut Vec<usize>) {}
+	fn for_each(&self, f: &mut dyn FnMut(&E)) { f(self) }
+	fn build(f: &mut dyn FnMut() -> E) -> E { f() }
+}
+
+impl<T: Array, const N: usize> Array for Vector<T, N> {
+	type Element = T::Element;
+	fn storage_shape(shape: &mut Vec<usize>) {
+		shape.push(N);
+		T::storage_shape(shape);
+	}
+	fn for_each(&self, f: &mut dyn FnMut(&T::Element)) {
+		self.inner.iter().for_each(|t| t.for_each(f))
+	}
+	fn build(f: &mut dyn FnMut() -> T::Element) -> Self {
+		use crate::types::Stupidity;
+		Vector::build_with_fn(|_| T::build(f))
+	}
+}
+
+/// what went wrong reading a .npy file
+#[derive(Debug)]
+pub enum NpyError {
+	Io(io::Error),
+	/// not a .npy file or a header that could not be understood
+	Header(String),
+	/// the file contains a different element type
+	Dtype { expected: &'static str, found: String },
+	/// the file contains an array of a different shape, in numpy's order
+	Shape { expected: Vec<usize>, found: Vec<usize> },
+}
+
+impl fmt::Display for NpyError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			NpyError::Io(e) => write!(f, "io error reading npy file: {}", e),
+			NpyError::Header(e) => write!(f, "invalid npy header: {}", e),
+			NpyError::Dtype { expected, found } => {
+				write!(f, "npy dtype mismatch: expected '{}' but found '{}'", expected, found)
+			},
+			NpyError::Shape { expected, found } => {
+				write!(f, "npy shape mismatch: expected {:?} but found {:?}", expected, found)
+			},
+		}
+	}
+}
+
+impl std::error::Error for NpyError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			NpyError::Io(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for NpyError {
+	fn from(e: io::Error) -> Self { NpyError::Io(e) }
+}
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// the shape as numpy sees it, innermost Vector first
+fn numpy_shape<A: Array>() -> Vec<usize> {
+	let mut shape = Vec::new();
+	A::storage_shape(&mut shape);
+	shape.reverse();
+	shape
+}
+
+fn shape_literal(shape: &[usize]) -> String {
+	match shape {
+		[single] => format!("({},)", single),
+		_ => {
+			let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+			format!("({})", dims.join(", "))
+		},
+	}
+}
+
+impl<T, const N: usize> Vector<T, N>
+where
+	Self: Array,
+{
+	/// writes a version 1.0 .npy file in fortran order
+	pub fn write_npy<W: Write>(&self, mut writer: W) -> io::Result<()> {
+		let mut header = format!(
+			"{{'descr': '{}', 'fortran_order': True, 'shape': {}, }}",
+			<Self as Array>::Element::DESCR,
+			shape_literal(&numpy_shape::<Self>()),
+		);
+		// magic, version and header length come first, the data starts 64 byte aligned
+		let unpadded = MAGIC.len() + 4 + header.len() + 1;
+		header.extend(core::iter::repeat_n(' ', (64 - unpadded % 64) % 64));
+		header.push('\n');
+
+		writer.write_all(MAGIC)?;
+		writer.write_all(&[1, 0])?;
+		writer.write_all(&(header.len() as u16).to_le_bytes())?;
+		writer.write_all(header.as_bytes())?;
+		let mut result = Ok(());
+		self.for_each(&mut |e| {
+			if result.is_ok() {
+				// elements are never padded
+				result = writer.write_all(pod::as_bytes(e).unwrap());
+			}
+		});
+		result
+	}
+
+	/// reads a .npy file, its dtype and shape have to match Self exactly
+	pub fn read_npy<R: Read>(mut reader: R) -> Result<Self, NpyError> {
+		let header = read_header(&mut reader)?;
+		let descr = dict_value(&header, "descr")?.trim_matches(['\'', '"']);
+		let expected = <Self as Array>::Element::DESCR;
+		if descr != expected {
+			return Err(NpyError::Dtype { expected, found: descr.to_string() });
+		}
+		let fortran_order = match dict_value(&header, "fortran_order")? {
+			"True" => true,
+			"False" => false,
+			other => return Err(NpyError::Header(format!("fortran_order is {}", other))),
+		};
+		let found = parse_shape(dict_value(&header, "shape")?)?;
+		let expected = numpy_shape::<Self>();
+		if found != expected {
+			return Err(NpyError::Shape { expected, found });
+		}
+
+		let size = core::mem::size_of::<<Self as Array>::Element>();
+		let count = expected.iter().product::<usize>();
+		let mut data = vec![0; size * count];
+		reader.read_exact(&mut data)?;
+		let element = |i: usize| {
+			let mut e = <Self as Array>::Element::default();
+			pod::as_bytes_mut(&mut e).unwrap().copy_from_slice(&data[i * size..][..size]);
+			e
+		};
+
+		let mut position = 0;
+		if fortran_order {
+			// fortran order over numpy's shape is the memory order of nested Vectors
+			return Ok(Self::build(&mut || {
+				position += 1;
+				element(position - 1)
+			}));
+		}
+		// C order: numpy's first axis counts fastest in memory but slowest in the file
+		let mut strides = vec![1; expected.len()];
+		for axis in (1..expected.len()).rev() {
+			strides[axis - 1] = strides[axis] * expected[axis];
+		}
+		Ok(Self::build(&mut || {
+			let (mut rest, mut offset) = (position, 0);
+			for (dim, stride) in expected.iter().zip(&strides) {
+				offset += rest % dim * stride;
+				rest /= dim;
+			}
+			position += 1;
+			element(offset)
+		}))
+	}
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<String, NpyError> {
+	let mut start = [0; 8];
+	reader.read_exact(&mut start)?;
+	if &start[..6] != MAGIC {
+		return Err(NpyError::Header("not a npy file".to_string()));
+	}
+	let len = match start[6] {
+		1 => {
+			let mut len = [0; 2];
+			reader.read_exact(&mut len)?;
+			u16::from_le_bytes(len) as usize
+		},
+		2 | 3 => {
+			let mut len = [0; 4];
+			reader.read_exact(&mut len)?;
+			u32::from_le_bytes(len) as usize
+		},
+		version => return Err(NpyError::Header(format!("unknown version {}", version))),
+	};
+	let mut header = vec![0; len];
+	reader.read_exact(&mut header)?;
+	String::from_utf8(header).map_err(|_| NpyError::Header("header is not utf-8".to_string()))
+}
+
+/// the text of the value for key in the header dict, without trailing whitespace
+fn dict_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+	let missing = || NpyError::Header(format!("missing key {}", key));
+	let start = header
+		.find(&format!("'{}'", key))
+		.or_else(|| header.find(&format!("\"{}\"", key)))
+		.ok_or_else(missing)?;
+	let rest = header[start + key.len() + 2..].trim_start();
+	let rest = rest.strip_prefix(':').ok_or_else(missing)?.trim_start();
+	// values are strings, booleans or tuples, none of them contain a comma outside parentheses
+	let end = if rest.starts_with('(') {
+		rest.find(')').map(|i| i + 1)
+	} else {
+		rest.find([',', '}'])
+	};
+	Ok(rest[..end.ok_or_else(missing)?].trim_end())
+}
+
+fn parse_shape(shape: &str) -> Result<Vec<usize>, NpyError> {
+	let inner = shape
+		.strip_prefix('(')
+		.and_then(|s| s.strip_suffix(')'))
+		.ok_or_else(|| NpyError::Header(format!("shape {} is not a tuple", shape)))?;
+	inner
+		.split(',')
+		.map(str::trim)
+		.filter(|d| !d.is_empty())
+		.map(|d| d.parse().map_err(|_| NpyError::Header(format!("invalid dimension {}", d))))
+		.collect()
+}
+
+#[cfg(test)]
+fn npy_file(header: &str, data: &[u8]) -> Vec<u8> {
+	let mut file = MAGIC.to_vec();
+	file.extend_from_slice(&[1, 0]);
+	file.extend_from_slice(&(header.len() as u16).to_le_bytes());
+	file.extend_from_slice(header.as_bytes());
+	file.extend_from_slice(data);
+	file
+}
+
+#[test]
+fn header() {
+	let v: Vector<f32, 3> = Vector::from_array([1., 2., 3.]);
+	let mut file = Vec::new();
+	v.write_npy(&mut file).unwrap();
+	// the data starts 64 byte aligned
+	let data_start = file.len() - 12;
+	assert_eq!(data_start % 64, 0);
+	let header = core::str::from_utf8(&file[10..data_start]).unwrap();
+	assert!(header.starts_with("{'descr': '<f4', 'fortran_order': True, 'shape': (3,), }"));
+	assert!(header.ends_with(" \n"));
+	assert_eq!(Vector::<f32, 3>::read_npy(&file[..]).unwrap(), v);
+
+	use crate::Stupidity;
+	let t: Vector<crate::Matrix<u8, 2, 3>, 4> = Vector::build_with_fn(|i| {
+		crate::Matrix::from_fn(|row, column| (i * 6 + column * 2 + row) as u8)
+	});
+	let mut file = Vec::new();
+	t.write_npy(&mut file).unwrap();
+	assert!(core::str::from_utf8(&file[10..]).unwrap().contains("'shape': (2, 3, 4)"));
+	// the data is just the memory
+	assert!(file.ends_with(&(0..24).collect::<Vec<u8>>()));
+	assert_eq!(<Vector<crate::Matrix<u8, 2, 3>, 4>>::read_npy(&file[..]).unwrap(), t);
+}
+
+#[test]
+fn c_order() {
+	// np.save of np.array([[1, 2, 3], [4, 5, 6]], dtype=np.int16)
+	let header = "{'descr': '<i2', 'fortran_order': False, 'shape': (2, 3), }\n";
+	let data: Vec<u8> = (1..=6i16).flat_map(|i| i.to_le_bytes()).collect();
+	let m = crate::Matrix::<i16, 2, 3>::read_npy(&npy_file(header, &data)[..]).unwrap();
+	assert_eq!(m[0], Vector::from_array([1, 4]));
+	assert_eq!(m[2], Vector::from_array([3, 6]));
+}
+
+#[test]
+fn errors() {
+	let header = "{'descr': '<f8', 'fortran_order': True, 'shape': (2,), }\n";
+	let file = npy_file(header, &[0; 16]);
+	let error = Vector::<f32, 2>::read_npy(&file[..]).unwrap_err();
+	assert_eq!(error.to_string(), "npy dtype mismatch: expected '<f4' but found '<f8'");
+	let error = Vector::<f64, 3>::read_npy(&file[..]).unwrap_err();
+	assert_eq!(error.to_string(), "npy shape mismatch: expected [3] but found [2]");
+	assert!(Vector::<f64, 2>::read_npy(&file[..]).is_ok());
+	assert!(matches!(Vector::<f64, 2>::read_npy(&file[..20]), Err(NpyError::Io(_))));
+	assert!(matches!(Vector::<f64, 2>::read_npy(&b"PK\x03\x04"[..]), Err(NpyError::Io(_))));
+	assert!(matches!(
+		Vector::<f64, 2>::read_npy(&b"PK\x03\x04 zip file"[..]),
+		Err(NpyError::Header(_))
+	));
+}
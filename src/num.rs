@@ -0,0 +1,137 @@
+//! minimal numeric traits, so constructors like identity matrices can be generic without pulling
+//! in a numerics crate
+use crate::types::{Stupidity, Vector};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// the additive identity
+pub trait Zero {
+	fn zero() -> Self;
+}
+
+/// the multiplicative identity
+pub trait One {
+	fn one() -> Self;
+}
+
+macro_rules! impl_identities {
+	( $zero:expr, $one:expr; $( $t:ty ), * ) => {
+		$(
+			impl Zero for $t {
+				fn zero() -> Self { $zero }
+			}
+
+			impl One for $t {
+				fn one() -> Self { $one }
+			}
+		)*
+	};
+}
+
+/// self * a + b, floats round only once
+pub trait MulAdd {
+	fn mul_add(self, a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_mul_add {
+	( $( $t:ty ), * ) => {
+		$(
+			impl MulAdd for $t {
+				#[inline]
+				fn mul_add(self, a: Self, b: Self) -> Self { self * a + b }
+			}
+		)*
+	};
+}
+
+impl_identities!(0, 1; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_identities!(0., 1.; f32, f64);
+
+impl_mul_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// the float functions geometry needs. core has none of them without std, so this uses libm
+pub trait Float:
+	Copy
+	+ PartialOrd
+	+ Zero
+	+ One
+	+ MulAdd
+	+ Add<Output = Self>
+	+ Sub<Output = Self>
+	+ Mul<Output = Self>
+	+ Div<Output = Self>
+	+ Neg<Output = Self>
+{
+	const EPSILON: Self;
+
+	/// rounds to the nearest representable value
+	fn from_f64(value: f64) -> Self;
+
+	fn sqrt(self) -> Self;
+	fn abs(self) -> Self;
+	fn sin(self) -> Self;
+	fn cos(self) -> Self;
+	fn acos(self) -> Self;
+	fn asin(self) -> Self;
+	fn atan2(self, other: Self) -> Self;
+	fn ln(self) -> Self;
+	fn powf(self, exponent: Self) -> Self;
+}
+
+/// keeps x in [-1, 1], rounding can push cosines of (anti)parallel vectors just outside of it
+pub(crate) fn clamp_unit<T: Float>(x: T) -> T {
+	let one = T::one();
+	if x > one {
+		one
+	} else if x < -one {
+		-one
+	} else {
+		x
+	}
+}
+
+macro_rules! impl_float {
+	(
+		$t:ident;
+		$sqrt:ident, $abs:ident, $sin:ident, $cos:ident, $acos:ident, $asin:ident, $atan2:ident,
+		$ln:ident, $powf:ident
+	) => {
+		impl Float for $t {
+			const EPSILON: Self = $t::EPSILON;
+
+			fn from_f64(value: f64) -> Self { value as $t }
+
+			fn sqrt(self) -> Self { libm::$sqrt(self) }
+			fn abs(self) -> Self { libm::$abs(self) }
+			fn sin(self) -> Self { libm::$sin(self) }
+			fn cos(self) -> Self { libm::$cos(self) }
+			fn acos(self) -> Self { libm::$acos(self) }
+			fn asin(self) -> Self { libm::$asin(self) }
+			fn atan2(self, other: Self) -> Self { libm::$atan2(self, other) }
+			fn ln(self) -> Self { libm::$ln(self) }
+			fn powf(self, exponent: Self) -> Self { libm::$powf(self, exponent) }
+		}
+	};
+}
+
+impl_float!(f32; sqrtf, fabsf, sinf, cosf, acosf, asinf, atan2f, logf, powf);
+impl_float!(f64; sqrt, fabs, sin, cos, acos, asin, atan2, log, pow);
+
+impl MulAdd for f32 {
+	#[inline]
+	fn mul_add(self, a: Self, b: Self) -> Self { libm::fmaf(self, a, b) }
+}
+
+impl MulAdd for f64 {
+	#[inline]
+	fn mul_add(self, a: Self, b: Self) -> Self { libm::fma(self, a, b) }
+}
+
+/// element-wise, so Matrix::zero() is a matrix full of zeros
+impl<T: Zero, const N: usize> Zero for Vector<T, N> {
+	fn zero() -> Self { Vector::build_with_fn(|_| T::zero()) }
+}
+
+/// element-wise, so Matrix::one() is a matrix full of ones, not the identity matrix
+impl<T: One, const N: usize> One for Vector<T, N> {
+	fn one() -> Self { Vector::build_with_fn(|_| T::one()) }
+}
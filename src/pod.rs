@@ -0,0 +1,168 @@
+//! reinterpreting Vectors as bytes and back without copying, for gpu buffers, memory maps and the
+//! network.
+//!
+//! Vector is aligned to 16 bytes, so some of them end in padding (a `Vector<f32, 3>` is 16 bytes
+//! long). padding can not be read as bytes and would not line up with a slice of elements, so
+//! casts involving padded types return [CastError::Padding]:
+//! ```
+//! use optimath::{pod, CastError, Vector};
+//! let v: Vector<f32, 4> = Vector::from_array([1., 2., 3., 4.]);
+//! let bytes = pod::as_bytes(&v).unwrap();
+//! assert_eq!(bytes.len(), 16);
+//! assert_eq!(pod::from_bytes::<Vector<f32, 4>>(bytes), Ok(&v));
+//!
+//! let vectors = [v, v];
+//! let floats: &[f32] = pod::cast_slice(&vectors).unwrap();
+//! assert_eq!(pod::cast_slice::<f32, Vector<f32, 4>>(floats).map(|s| s.len()), Ok(2));
+//! assert_eq!(pod::cast_slice::<f32, Vector<f32, 3>>(floats), Err(CastError::Padding));
+//! ```
+use crate::{complex::Complex, types::Vector};
+use core::mem::{align_of, size_of, size_of_val};
+
+/// plain old data: every bit pattern is a valid value and there are no pointers inside.
+///
+/// # Safety
+/// all bytes of Self have to be initialized unless PADDED is true, and any bytes have to form a
+/// valid Self.
+pub unsafe trait Pod: Copy + 'static {
+	/// whether Self contains padding bytes, those make it impossible to view it as bytes
+	const PADDED: bool = false;
+}
+
+macro_rules! impl_pod {
+	( $( $t:ty ), * ) => {
+		$(
+			unsafe impl Pod for $t {}
+		)*
+	};
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// the elements are followed by padding up to the alignment of 16
+unsafe impl<T: Pod, const N: usize> Pod for Vector<T, N> {
+	const PADDED: bool = T::PADDED || size_of::<Self>() != N * size_of::<T>();
+}
+
+// repr(C) with two fields of the same type
+unsafe impl<T: Pod> Pod for Complex<T> {
+	const PADDED: bool = T::PADDED;
+}
+
+/// why a cast was not possible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastError {
+	/// one of the types contains padding
+	Padding,
+	/// the data is not aligned for the target type
+	Alignment,
+	/// the length in bytes does not fit the target type
+	Size,
+}
+
+impl core::fmt::Display for CastError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.write_str(match self {
+			CastError::Padding => "cast involving a type with padding",
+			CastError::Alignment => "cast to a type with a higher alignment than the data",
+			CastError::Size => "cast to a type that does not fit the length of the data",
+		})
+	}
+}
+
+/// the checks shared by all casts, returns the length of the target slice
+fn check<A: Pod, B: Pod>(data: &[A]) -> Result<usize, CastError> {
+	if A::PADDED || B::PADDED {
+		return Err(CastError::Padding);
+	}
+	if !(data.as_ptr() as usize).is_multiple_of(align_of::<B>()) {
+		return Err(CastError::Alignment);
+	}
+	let bytes = size_of_val(data);
+	match size_of::<B>() {
+		0 if bytes == 0 => Ok(0),
+		0 => Err(CastError::Size),
+		size if bytes.is_multiple_of(size) => Ok(bytes / size),
+		_ => Err(CastError::Size),
+	}
+}
+
+/// views a slice of one Pod type as a slice of another
+pub fn cast_slice<A: Pod, B: Pod>(data: &[A]) -> Result<&[B], CastError> {
+	let len = check::<A, B>(data)?;
+	// no padding on either side, aligned and the length fits
+	Ok(unsafe { core::slice::from_raw_parts(data.as_ptr() as *const B, len) })
+}
+
+/// mutable version of [cast_slice]
+pub fn cast_slice_mut<A: Pod, B: Pod>(data: &mut [A]) -> Result<&mut [B], CastError> {
+	let len = check::<A, B>(data)?;
+	Ok(unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut B, len) })
+}
+
+/// the bytes of value, in native endianness
+pub fn as_bytes<T: Pod>(value: &T) -> Result<&[u8], CastError> {
+	cast_slice(core::slice::from_ref(value))
+}
+
+/// mutable version of [as_bytes]
+pub fn as_bytes_mut<T: Pod>(value: &mut T) -> Result<&mut [u8], CastError> {
+	cast_slice_mut(core::slice::from_mut(value))
+}
+
+/// views bytes as a T, they have to be exactly as long as T and aligned for it
+pub fn from_bytes<T: Pod>(bytes: &[u8]) -> Result<&T, CastError> {
+	match cast_slice(bytes)? {
+		[value] => Ok(value),
+		_ => Err(CastError::Size),
+	}
+}
+
+/// mutable version of [from_bytes]
+pub fn from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T, CastError> {
+	match cast_slice_mut(bytes)? {
+		[value] => Ok(value),
+		_ => Err(CastError::Size),
+	}
+}
+
+#[test]
+fn padding() {
+	use crate::Matrix;
+	let padded = [
+		<Vector<f32, 4>>::PADDED,
+		<Vector<f32, 3>>::PADDED,
+		<Vector<f32, 2>>::PADDED,
+		<Vector<u8, 32>>::PADDED,
+		<Matrix<f32, 4, 4>>::PADDED,
+		// the columns are padded, the outer Vector is not
+		<Matrix<f32, 3, 4>>::PADDED,
+		<Complex<f64>>::PADDED,
+	];
+	assert_eq!(padded, [false, true, true, false, false, true, false]);
+}
+
+#[test]
+fn casts() {
+	use crate::Matrix;
+	let mut m: Matrix<f32, 4, 2> = Matrix::from_fn(|row, column| (row + 4 * column) as f32);
+	let floats: &[f32] = cast_slice(core::slice::from_ref(&m)).unwrap();
+	assert_eq!(floats, &[0., 1., 2., 3., 4., 5., 6., 7.][..]);
+	let columns: &[Vector<f32, 4>] = cast_slice(floats).unwrap();
+	assert_eq!(columns, m.as_slice());
+
+	let bytes = as_bytes_mut(&mut m).unwrap();
+	assert_eq!(bytes.len(), 32);
+	bytes[..4].copy_from_slice(&9f32.to_ne_bytes());
+	assert_eq!(m[0][0], 9.);
+
+	let bytes = as_bytes(&m).unwrap();
+	assert_eq!(from_bytes::<Vector<f32, 4>>(&bytes[..16]).unwrap(), &m[0]);
+	assert_eq!(from_bytes::<Vector<f32, 4>>(&bytes[..12]), Err(CastError::Size));
+	assert_eq!(from_bytes::<Vector<f32, 4>>(&bytes[4..20]), Err(CastError::Alignment));
+	assert_eq!(cast_slice::<u8, u32>(&bytes[..6]), Err(CastError::Size));
+	assert_eq!(as_bytes(&Vector::from_array([1u8, 2, 3])), Err(CastError::Padding));
+
+	let complex = [Complex::new(1., 2.), Complex::new(3., 4.)];
+	assert_eq!(cast_slice::<Complex<f64>, f64>(&complex), Ok(&[1., 2., 3., 4.][..]));
+}
@@ -0,0 +1,450 @@
+//! printing Vectors, Matrices and higher rank tensors in a readable way.
+//!
+//! Display lays matrices out as a grid of rows, like numpy does. precision and width flags apply
+//! to every element and all elements are padded to the same width:
+//! ```
+//! use optimath::{Matrix, Vector};
+//! let m: Matrix<f32, 2, 3> = Matrix::from_fn(|row, column| row as f32 - column as f32 / 2.);
+//! assert_eq!(format!("{:.1}", m), "[[ 0.0 -0.5 -1.0]\n [ 1.0  0.5  0.0]]");
+//! assert_eq!(format!("{}", Vector::from_array([1, 20, 3])), "[ 1 20  3]");
+//! ```
+//! tensors of a rank above 2 are printed as a list of matrices, the outermost Vector picks the
+//! matrix. axes longer than ELIDE_ABOVE only show their first and last EDGE_ITEMS elements.
+//! use `{:?}` (or `{:#}`) for the plain nested representation.
+//!
+//! [to_numpy_literal](crate::Vector::to_numpy_literal) and [to_latex](crate::Vector::to_latex)
+//! return adapters for pasting the values somewhere else.
+use crate::types::Vector;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+/// axes with more elements than this get shortened
+pub const ELIDE_ABOVE: usize = 10;
+/// how many elements are shown on either side of the "…"
+pub const EDGE_ITEMS: usize = 3;
+
+// deep enough for anything sensible, only the innermost MAX_RANK axes of deeper tensors are
+// aligned
+const MAX_RANK: usize = 8;
+
+/// shape and elements of arbitrarily nested Vectors.
+///
+/// the axes are in storage order, outermost first.
+pub(crate) trait Tensor {
+	fn rank() -> usize;
+	fn dim(axis: usize) -> usize;
+	fn scalar(&self, index: &[usize]) -> &dyn Scalar;
+}
+
+impl<T: Display> Tensor for T {
+	default fn rank() -> usize { 0 }
+	default fn dim(_axis: usize) -> usize { 1 }
+	default fn scalar(&self, _index: &[usize]) -> &dyn Scalar { self }
+}
+
+impl<T: Display + Debug, const N: usize> Tensor for Vector<T, N> {
+	fn rank() -> usize { T::rank() + 1 }
+	fn dim(axis: usize) -> usize {
+		if axis == 0 {
+			N
+		} else {
+			T::dim(axis - 1)
+		}
+	}
+	fn scalar(&self, index: &[usize]) -> &dyn Scalar { self[index[0]].scalar(&index[1..]) }
+}
+
+/// an element as it is written into the adapters
+pub(crate) trait Scalar: Display {
+	fn write_numpy(&self, out: &mut dyn Write, precision: Option<usize>) -> fmt::Result;
+}
+
+impl<T: Display> Scalar for T {
+	default fn write_numpy(&self, out: &mut dyn Write, precision: Option<usize>) -> fmt::Result {
+		write_scalar(out, self, precision)
+	}
+}
+
+// numpy makes an int array out of a list without a single float literal in it
+macro_rules! float_scalar {
+	( $( $t:ty ), * ) => {
+		$(
+			impl Scalar for $t {
+				fn write_numpy(
+					&self,
+					out: &mut dyn Write,
+					precision: Option<usize>,
+				) -> fmt::Result {
+					if self.is_nan() {
+						return out.write_str("np.nan");
+					}
+					if self.is_infinite() {
+						return out.write_str(if *self > 0. { "np.inf" } else { "-np.inf" });
+					}
+					let mut marked = FloatMarked { out, marked: false };
+					write_scalar(&mut marked, self, precision)?;
+					if marked.marked {
+						Ok(())
+					} else {
+						marked.out.write_str(".0")
+					}
+				}
+			}
+		)*
+	};
+}
+
+float_scalar!(f32, f64);
+
+/// remembers whether a decimal point or an exponent went through
+struct FloatMarked<'a> {
+	out: &'a mut dyn Write,
+	marked: bool,
+}
+
+impl Write for FloatMarked<'_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.marked |= s.contains(['.', 'e', 'E']);
+		self.out.write_str(s)
+	}
+}
+
+/// the axes in printing order: the slices, then rows, then columns.
+///
+/// a Matrix stores columns on the outside, so the last two axes are swapped.
+struct Layout {
+	rank: usize,
+	dims: [usize; MAX_RANK],
+}
+
+impl Layout {
+	fn new<V: Tensor>() -> Self {
+		let rank = V::rank();
+		let mut dims = [0; MAX_RANK];
+		for (axis, dim) in dims.iter_mut().enumerate().take(rank) {
+			*dim = V::dim(axis);
+		}
+		if (2..=MAX_RANK).contains(&rank) {
+			dims.swap(rank - 2, rank - 1);
+		}
+		Layout { rank, dims }
+	}
+
+	fn scalar<'a, V: Tensor>(&self, value: &'a V, index: &[usize; MAX_RANK]) -> &'a dyn Scalar {
+		let mut storage = *index;
+		if self.rank >= 2 {
+			storage.swap(self.rank - 2, self.rank - 1);
+		}
+		value.scalar(&storage[..self.rank])
+	}
+
+	/// calls f with every printed index of the axis, None stands for the elided middle
+	fn visit(&self, axis: usize, mut f: impl FnMut(Option<usize>) -> fmt::Result) -> fmt::Result {
+		let n = self.dims[axis];
+		if n > ELIDE_ABOVE {
+			(0..EDGE_ITEMS).try_for_each(|i| f(Some(i)))?;
+			f(None)?;
+			(n - EDGE_ITEMS..n).try_for_each(|i| f(Some(i)))
+		} else {
+			(0..n).try_for_each(|i| f(Some(i)))
+		}
+	}
+
+	/// calls f with every printed element
+	fn for_each<'a, V: Tensor>(
+		&self,
+		value: &'a V,
+		f: &mut dyn FnMut(&'a dyn Display) -> fmt::Result,
+	) -> fmt::Result {
+		fn go<'a, V: Tensor>(
+			layout: &Layout,
+			value: &'a V,
+			index: &mut [usize; MAX_RANK],
+			axis: usize,
+			f: &mut dyn FnMut(&'a dyn Display) -> fmt::Result,
+		) -> fmt::Result {
+			if axis == layout.rank {
+				return f(layout.scalar(value, index));
+			}
+			layout.visit(axis, |i| match i {
+				Some(i) => {
+					index[axis] = i;
+					go(layout, value, index, axis + 1, f)
+				},
+				None => Ok(()),
+			})
+		}
+		go(self, value, &mut [0; MAX_RANK], 0, f)
+	}
+}
+
+/// counts the characters written
+struct Counter(usize);
+
+impl Write for Counter {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.0 += s.chars().count();
+		Ok(())
+	}
+}
+
+fn write_scalar(out: &mut dyn Write, x: &dyn Display, precision: Option<usize>) -> fmt::Result {
+	match precision {
+		Some(precision) => write!(out, "{:.*}", precision, x),
+		None => write!(out, "{}", x),
+	}
+}
+
+fn scalar_width(x: &dyn Display, precision: Option<usize>) -> usize {
+	let mut counter = Counter(0);
+	// Counter never fails, and a failing Display shows up when actually printing
+	let _ = write_scalar(&mut counter, x, precision);
+	counter.0
+}
+
+/// pads to width, on the right unless left alignment was asked for
+fn write_padded(f: &mut Formatter<'_>, x: &dyn Display, width: usize) -> fmt::Result {
+	let padding = width.saturating_sub(scalar_width(x, f.precision()));
+	let left = f.align() == Some(fmt::Alignment::Left);
+	if !left {
+		(0..padding).try_for_each(|_| f.write_char(' '))?;
+	}
+	let precision = f.precision();
+	write_scalar(f, x, precision)?;
+	if left {
+		(0..padding).try_for_each(|_| f.write_char(' '))?;
+	}
+	Ok(())
+}
+
+fn write_grid<V: Tensor>(value: &V, f: &mut Formatter<'_>) -> fmt::Result {
+	let layout = Layout::new::<V>();
+	let mut width = f.width().unwrap_or(0);
+	let precision = f.precision();
+	layout.for_each(value, &mut |x| {
+		width = width.max(scalar_width(x, precision));
+		Ok(())
+	})?;
+
+	fn go<V: Tensor>(
+		layout: &Layout,
+		value: &V,
+		f: &mut Formatter<'_>,
+		width: usize,
+		index: &mut [usize; MAX_RANK],
+		axis: usize,
+	) -> fmt::Result {
+		f.write_char('[')?;
+		let last = axis + 1 == layout.rank;
+		let mut first = true;
+		layout.visit(axis, |i| {
+			if !first {
+				if last {
+					f.write_char(' ')?;
+				} else {
+					// one blank line between matrices, two between stacks of matrices, ...
+					(axis + 1..layout.rank).try_for_each(|_| f.write_char('\n'))?;
+					(0..=axis).try_for_each(|_| f.write_char(' '))?;
+				}
+			}
+			first = false;
+			match i {
+				Some(i) => {
+					index[axis] = i;
+					if last {
+						write_padded(f, layout.scalar(value, index), width)
+					} else {
+						go(layout, value, f, width, index, axis + 1)
+					}
+				},
+				None => f.write_char('…'),
+			}
+		})?;
+		f.write_char(']')
+	}
+	go(&layout, value, f, width, &mut [0; MAX_RANK], 0)
+}
+
+/// a grid of rows, see the [module documentation](self). the alternate flag prints like Debug
+impl<T: Display + Debug, const N: usize> Display for Vector<T, N> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if f.alternate() {
+			return Debug::fmt(self, f);
+		}
+		if Self::rank() <= MAX_RANK {
+			return write_grid(self, f);
+		}
+		f.write_char('[')?;
+		for (i, x) in self.into_iter().enumerate() {
+			if i > 0 {
+				f.write_str(",\n")?;
+			}
+			Display::fmt(x, f)?;
+		}
+		f.write_char(']')
+	}
+}
+
+/// prints a numpy.array call with the values, nothing is elided
+pub struct NumpyLiteral<'a, V>(&'a V);
+
+/// prints a bmatrix for LaTeX, tensors of higher rank become a row of matrices
+pub struct Latex<'a, V>(&'a V);
+
+impl<T: Display, const N: usize> Vector<T, N> {
+	/// `np.array([[1, 2], [3, 4]])`, honours the precision flag. floats always get a decimal point
+	/// so numpy keeps them floats
+	pub fn to_numpy_literal(&self) -> NumpyLiteral<'_, Self> { NumpyLiteral(self) }
+
+	/// `\begin{bmatrix} 1 & 2 \\ 3 & 4 \end{bmatrix}`, honours the precision flag.
+	///
+	/// a Vector is printed as a column.
+	pub fn to_latex(&self) -> Latex<'_, Self> { Latex(self) }
+}
+
+impl<'a, T: Display + Debug, const N: usize> Display for NumpyLiteral<'a, Vector<T, N>> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let layout = Layout::new::<Vector<T, N>>();
+		if layout.rank > MAX_RANK {
+			return Err(fmt::Error);
+		}
+
+		fn go<T: Tensor>(
+			layout: &Layout,
+			value: &T,
+			f: &mut Formatter<'_>,
+			index: &mut [usize; MAX_RANK],
+			axis: usize,
+		) -> fmt::Result {
+			if axis == layout.rank {
+				let precision = f.precision();
+				return layout.scalar(value, index).write_numpy(f, precision);
+			}
+			f.write_char('[')?;
+			for i in 0..layout.dims[axis] {
+				if i > 0 {
+					f.write_str(", ")?;
+				}
+				index[axis] = i;
+				go(layout, value, f, index, axis + 1)?;
+			}
+			f.write_char(']')
+		}
+		f.write_str("np.array(")?;
+		go(&layout, self.0, f, &mut [0; MAX_RANK], 0)?;
+		f.write_char(')')
+	}
+}
+
+impl<'a, T: Display + Debug, const N: usize> Display for Latex<'a, Vector<T, N>> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let layout = Layout::new::<Vector<T, N>>();
+		if layout.rank > MAX_RANK {
+			return Err(fmt::Error);
+		}
+		let precision = f.precision();
+		let (value, mut index) = (self.0, [0; MAX_RANK]);
+		let rank = layout.rank;
+		let matrix = |f: &mut Formatter<'_>, index: &mut [usize; MAX_RANK]| {
+			f.write_str("\\begin{bmatrix} ")?;
+			let (rows, columns) = match rank {
+				1 => (layout.dims[0], 1),
+				_ => (layout.dims[rank - 2], layout.dims[rank - 1]),
+			};
+			for row in 0..rows {
+				if row > 0 {
+					f.write_str(" \\\\ ")?;
+				}
+				for column in 0..columns {
+					if column > 0 {
+						f.write_str(" & ")?;
+					}
+					match rank {
+						1 => index[0] = row,
+						_ => {
+							index[rank - 2] = row;
+							index[rank - 1] = column;
+						},
+					}
+					write_scalar(f, layout.scalar(value, index), precision)?;
+				}
+			}
+			f.write_str(" \\end{bmatrix}")
+		};
+		if rank <= 2 {
+			return matrix(f, &mut index);
+		}
+		// every combination of the slice indices, the last one counting fastest
+		let slices: usize = layout.dims[..rank - 2].iter().product();
+		for slice in 0..slices {
+			if slice > 0 {
+				f.write_char(' ')?;
+			}
+			let mut rest = slice;
+			for axis in (0..rank - 2).rev() {
+				index[axis] = rest % layout.dims[axis];
+				rest /= layout.dims[axis];
+			}
+			matrix(f, &mut index)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+extern crate std;
+#[cfg(test)]
+use std::format;
+
+#[test]
+fn grid() {
+	use crate::Matrix;
+	let m: Matrix<i32, 2, 3> = Matrix::from_fn(|row, column| (10 * row + column) as i32);
+	assert_eq!(format!("{}", m), "[[ 0  1  2]\n [10 11 12]]");
+	assert_eq!(format!("{:3}", m), "[[  0   1   2]\n [ 10  11  12]]");
+	assert_eq!(format!("{:<2}", m), "[[0  1  2 ]\n [10 11 12]]");
+	assert_eq!(format!("{:.2}", Vector::from_array([0.5, -1.])), "[ 0.50 -1.00]");
+	assert_eq!(format!("{}", Vector::from_array([1u8; 0])), "[]");
+
+	let t: Vector<Matrix<i32, 2, 2>, 2> =
+		Vector::from_array([Matrix::from_fn(|r, c| (2 * r + c) as i32), Matrix::from_fn(|_, _| 9)]);
+	assert_eq!(format!("{}", t), "[[[0 1]\n  [2 3]]\n\n [[9 9]\n  [9 9]]]");
+	assert_eq!(format!("{:?}", Vector::from_array([1, 2])), "Vector<2>[1, 2]");
+	let v = Vector::from_array([1, 2]);
+	assert_eq!(format!("{:#}", v), format!("{:#?}", v));
+}
+
+#[test]
+fn elided() {
+	let v: Vector<i32, 12> = Vector::arange(0, 1);
+	assert_eq!(format!("{}", v), "[ 0  1  2 …  9 10 11]");
+	let m: crate::Matrix<i32, 12, 1> = v.ascend();
+	assert_eq!(format!("{}", m), "[[ 0]\n [ 1]\n [ 2]\n …\n [ 9]\n [10]\n [11]]");
+}
+
+#[test]
+fn adapters() {
+	use crate::{Matrix, Zero};
+	let m: Matrix<f64, 2, 2> = Matrix::from_fn(|row, column| (2 * row + column) as f64 / 4.);
+	assert_eq!(format!("{}", m.to_numpy_literal()), "np.array([[0.0, 0.25], [0.5, 0.75]])");
+	let v = Vector::from_array([1., 2.]);
+	assert_eq!(format!("{:.1}", v.to_numpy_literal()), "np.array([1.0, 2.0])");
+	assert_eq!(format!("{:.0}", v.to_numpy_literal()), "np.array([1.0, 2.0])");
+	let v = Vector::from_array([1e-7f32, f32::NAN, -f32::INFINITY]);
+	assert_eq!(format!("{}", v.to_numpy_literal()), "np.array([0.0000001, np.nan, -np.inf])");
+	assert_eq!(format!("{}", Vector::from_array([1, 2]).to_numpy_literal()), "np.array([1, 2])");
+	assert_eq!(
+		format!("{:.2}", m.to_latex()),
+		"\\begin{bmatrix} 0.00 & 0.25 \\\\ 0.50 & 0.75 \\end{bmatrix}"
+	);
+	assert_eq!(
+		format!("{}", Vector::from_array([1, 2]).to_latex()),
+		"\\begin{bmatrix} 1 \\\\ 2 \\end{bmatrix}"
+	);
+	let t: Vector<Matrix<i32, 1, 1>, 2> = Vector::from_array([Matrix::ones(), Matrix::zero()]);
+	assert_eq!(
+		format!("{}", t.to_latex()),
+		"\\begin{bmatrix} 1 \\end{bmatrix} \\begin{bmatrix} 0 \\end{bmatrix}"
+	);
+	let long: Vector<i32, 12> = Vector::arange(0, 1);
+	assert!(format!("{}", long.to_numpy_literal()).ends_with("9, 10, 11])"));
+}
@@ -0,0 +1,271 @@
+//! rotations in 3d as unit quaternions.
+//!
+//! stored as [x, y, z, w] so the vector part is the first three elements of the inner Vector
+use crate::{
+	num::{clamp_unit, Float},
+	types::{Matrix, Vector},
+};
+use core::ops::Mul;
+
+/// w + xi + yj + zk
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct Quaternion<T> {
+	pub(crate) inner: Vector<T, 4>,
+}
+
+impl<T> Quaternion<T> {
+	pub fn new(w: T, x: T, y: T, z: T) -> Self {
+		Quaternion {
+			inner: Vector::from_array([x, y, z, w]),
+		}
+	}
+
+	/// [x, y, z, w]
+	pub fn from_vector(inner: Vector<T, 4>) -> Self { Quaternion { inner } }
+	pub fn into_vector(self) -> Vector<T, 4> { self.inner }
+	pub fn as_vector(&self) -> &Vector<T, 4> { &self.inner }
+}
+
+impl<T: Copy> Quaternion<T> {
+	pub fn w(&self) -> T { self.inner[3] }
+	pub fn x(&self) -> T { self.inner[0] }
+	pub fn y(&self) -> T { self.inner[1] }
+	pub fn z(&self) -> T { self.inner[2] }
+
+	/// the imaginary part
+	pub fn vector(&self) -> Vector<T, 3> { self.inner.truncate() }
+}
+
+impl<T: Float> Quaternion<T> {
+	/// no rotation
+	pub fn identity() -> Self { Quaternion::new(T::one(), T::zero(), T::zero(), T::zero()) }
+
+	pub fn conjugate(&self) -> Self { Quaternion::new(self.w(), -self.x(), -self.y(), -self.z()) }
+
+	pub fn norm(&self) -> T { self.inner.norm() }
+
+	pub fn normalize(&self) -> Self { Quaternion::from_vector(self.inner.normalize()) }
+
+	/// for unit quaternions this is the same as the conjugate
+	pub fn inverse(&self) -> Self {
+		let norm_squared = self.inner.fdot(&self.inner);
+		let c = self.conjugate();
+		Quaternion::new(
+			c.w() / norm_squared,
+			c.x() / norm_squared,
+			c.y() / norm_squared,
+			c.z() / norm_squared,
+		)
+	}
+
+	/// the hamilton product, applying the result rotates by other first and then by self
+	pub fn hamilton(&self, other: &Self) -> Self {
+		let (a, b) = (self, other);
+		Quaternion::new(
+			a.w() * b.w() - a.x() * b.x() - a.y() * b.y() - a.z() * b.z(),
+			a.w() * b.x() + a.x() * b.w() + a.y() * b.z() - a.z() * b.y(),
+			a.w() * b.y() - a.x() * b.z() + a.y() * b.w() + a.z() * b.x(),
+			a.w() * b.z() + a.x() * b.y() - a.y() * b.x() + a.z() * b.w(),
+		)
+	}
+
+	/// rotates v, self has to be a unit quaternion
+	pub fn rotate(&self, v: &Vector<T, 3>) -> Vector<T, 3> {
+		// v + 2w(u × v) + 2u × (u × v), which is cheaper than q v q*
+		let u = self.vector();
+		let two = T::one() + T::one();
+		let t = u.cross(v);
+		let t = Vector::from_array([t[0] * two, t[1] * two, t[2] * two]);
+		let ut = u.cross(&t);
+		Vector::from_array([
+			self.w().mul_add(t[0], v[0] + ut[0]),
+			self.w().mul_add(t[1], v[1] + ut[1]),
+			self.w().mul_add(t[2], v[2] + ut[2]),
+		])
+	}
+
+	/// spherical interpolation along the shorter way, t = 0 is self and t = 1 is other
+	pub fn slerp(&self, other: &Self, t: T) -> Self {
+		// q and -q are the same rotation, pick the one on the near side
+		let other = if self.inner.fdot(&other.inner) < T::zero() {
+			Quaternion::new(-other.w(), -other.x(), -other.y(), -other.z())
+		} else {
+			*other
+		};
+		Quaternion::from_vector(self.inner.slerp(&other.inner, t).normalize())
+	}
+
+	/// rotation by angle radians around axis, axis does not need to be normalized
+	pub fn from_axis_angle(axis: &Vector<T, 3>, angle: T) -> Self {
+		let half = angle / (T::one() + T::one());
+		let (sin, cos) = (half.sin(), half.cos());
+		let axis = axis.normalize();
+		Quaternion::new(cos, axis[0] * sin, axis[1] * sin, axis[2] * sin)
+	}
+
+	/// (axis, angle), the angle is in [0, 2π]. without rotation the axis is arbitrarily x
+	pub fn to_axis_angle(&self) -> (Vector<T, 3>, T) {
+		let two = T::one() + T::one();
+		let angle = clamp_unit(self.w()).acos() * two;
+		let v = self.vector();
+		let sin = v.norm();
+		if sin <= T::EPSILON {
+			return (Vector::from_array([T::one(), T::zero(), T::zero()]), angle);
+		}
+		(Vector::from_array([v[0] / sin, v[1] / sin, v[2] / sin]), angle)
+	}
+
+	/// the rotation matrix of a unit quaternion
+	pub fn to_rotation_matrix(&self) -> Matrix<T, 3, 3> {
+		let (w, x, y, z) = (self.w(), self.x(), self.y(), self.z());
+		let one = T::one();
+		let two = one + one;
+		let rows = [
+			[one - two * (y * y + z * z), two * (x * y - z * w), two * (x * z + y * w)],
+			[two * (x * y + z * w), one - two * (x * x + z * z), two * (y * z - x * w)],
+			[two * (x * z - y * w), two * (y * z + x * w), one - two * (x * x + y * y)],
+		];
+		Matrix::from_fn(|row, column| rows[row][column])
+	}
+
+	/// expects a proper rotation matrix (orthogonal with determinant 1)
+	pub fn from_rotation_matrix(m: &Matrix<T, 3, 3>) -> Self {
+		// column major, so m(row, column) is m[column][row]
+		let at = |row: usize, column: usize| m[column][row];
+		let one = T::one();
+		let two = one + one;
+		let four = two + two;
+		let trace = at(0, 0) + at(1, 1) + at(2, 2);
+		// pick the largest of w, x, y and z to divide by, for numeric stability
+		if trace > T::zero() {
+			let s = (trace + one).sqrt() * two;
+			Quaternion::new(
+				s / four,
+				(at(2, 1) - at(1, 2)) / s,
+				(at(0, 2) - at(2, 0)) / s,
+				(at(1, 0) - at(0, 1)) / s,
+			)
+		} else if at(0, 0) > at(1, 1) && at(0, 0) > at(2, 2) {
+			let s = (one + at(0, 0) - at(1, 1) - at(2, 2)).sqrt() * two;
+			Quaternion::new(
+				(at(2, 1) - at(1, 2)) / s,
+				s / four,
+				(at(0, 1) + at(1, 0)) / s,
+				(at(0, 2) + at(2, 0)) / s,
+			)
+		} else if at(1, 1) > at(2, 2) {
+			let s = (one + at(1, 1) - at(0, 0) - at(2, 2)).sqrt() * two;
+			Quaternion::new(
+				(at(0, 2) - at(2, 0)) / s,
+				(at(0, 1) + at(1, 0)) / s,
+				s / four,
+				(at(1, 2) + at(2, 1)) / s,
+			)
+		} else {
+			let s = (one + at(2, 2) - at(0, 0) - at(1, 1)).sqrt() * two;
+			Quaternion::new(
+				(at(1, 0) - at(0, 1)) / s,
+				(at(0, 2) + at(2, 0)) / s,
+				(at(1, 2) + at(2, 1)) / s,
+				s / four,
+			)
+		}
+	}
+
+	/// intrinsic z-y'-x'' (yaw, pitch, roll) as used in aerospace.
+	///
+	/// rotates by roll around x first, then pitch around y, then yaw around z, all in radians.
+	pub fn from_euler_zyx(yaw: T, pitch: T, roll: T) -> Self {
+		let two = T::one() + T::one();
+		let (sy, cy) = ((yaw / two).sin(), (yaw / two).cos());
+		let (sp, cp) = ((pitch / two).sin(), (pitch / two).cos());
+		let (sr, cr) = ((roll / two).sin(), (roll / two).cos());
+		Quaternion::new(
+			cr * cp * cy + sr * sp * sy,
+			sr * cp * cy - cr * sp * sy,
+			cr * sp * cy + sr * cp * sy,
+			cr * cp * sy - sr * sp * cy,
+		)
+	}
+
+	/// (yaw, pitch, roll) in the same convention as [from_euler_zyx](#method.from_euler_zyx).
+	///
+	/// pitch is in [-π/2, π/2], at exactly ±π/2 yaw and roll are not unique (gimbal lock).
+	pub fn to_euler_zyx(&self) -> (T, T, T) {
+		let (w, x, y, z) = (self.w(), self.x(), self.y(), self.z());
+		let one = T::one();
+		let two = one + one;
+		let roll = (two * (w * x + y * z)).atan2(one - two * (x * x + y * y));
+		let pitch = clamp_unit(two * (w * y - z * x)).asin();
+		let yaw = (two * (w * z + x * y)).atan2(one - two * (y * y + z * z));
+		(yaw, pitch, roll)
+	}
+}
+
+/// the hamilton product
+impl<T: Float> Mul for Quaternion<T> {
+	type Output = Quaternion<T>;
+	fn mul(self, other: Self) -> Self { self.hamilton(&other) }
+}
+
+#[test]
+fn known_rotations() {
+	use core::f64::consts::{FRAC_PI_2, PI};
+	let x = Vector::from_array([1., 0., 0.]);
+	let y = Vector::from_array([0., 1., 0.]);
+	let z = Vector::from_array([0., 0., 1.]);
+
+	let quarter_z = Quaternion::from_axis_angle(&z, FRAC_PI_2);
+	crate::assert_approx_eq!(quarter_z.rotate(&x), y, epsilon = 1e-12);
+	crate::assert_approx_eq!(
+		quarter_z.rotate(&y),
+		Vector::from_array([-1., 0., 0.]),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(quarter_z.rotate(&z), z, epsilon = 1e-12);
+
+	// x by a quarter, then z by a quarter: y -> z -> z and z -> -y -> x
+	let quarter_x = Quaternion::from_axis_angle(&x, FRAC_PI_2);
+	let both = quarter_z * quarter_x;
+	crate::assert_approx_eq!(both.rotate(&y), z, epsilon = 1e-12);
+	crate::assert_approx_eq!(both.rotate(&z), x, epsilon = 1e-12);
+	crate::assert_approx_eq!(
+		(both * both.inverse()).into_vector(),
+		Quaternion::identity().into_vector(),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(both.conjugate().rotate(&both.rotate(&x)), x, epsilon = 1e-12);
+
+	let (axis, angle) = quarter_z.to_axis_angle();
+	crate::assert_approx_eq!(axis, z, epsilon = 1e-12);
+	assert!((angle - FRAC_PI_2).abs() < 1e-12);
+
+	let half_z = Quaternion::from_axis_angle(&z, PI);
+	let halfway = Quaternion::identity().slerp(&half_z, 0.5);
+	crate::assert_approx_eq!(halfway.into_vector(), quarter_z.into_vector(), epsilon = 1e-12);
+
+	let m = quarter_z.to_rotation_matrix();
+	crate::assert_approx_eq!(m.matrix_multiply(&x.ascend())[0], y, epsilon = 1e-12);
+	for q in &[quarter_z, quarter_x, both, half_z, Quaternion::from_axis_angle(&y, 3.)] {
+		let back = Quaternion::from_rotation_matrix(&q.to_rotation_matrix());
+		// q and -q are the same rotation
+		let sign = if back.w() * q.w() < 0. { -1. } else { 1. };
+		crate::assert_approx_eq!(
+			back.into_vector().lazy().map(|e| e * sign).realize(),
+			q.into_vector(),
+			epsilon = 1e-12,
+		);
+	}
+
+	let euler = Quaternion::from_euler_zyx(0.3, -0.2, 1.1);
+	let manual = Quaternion::from_axis_angle(&z, 0.3)
+		* Quaternion::from_axis_angle(&y, -0.2)
+		* Quaternion::from_axis_angle(&x, 1.1);
+	crate::assert_approx_eq!(euler.into_vector(), manual.into_vector(), epsilon = 1e-12);
+	let (yaw, pitch, roll) = euler.to_euler_zyx();
+	crate::assert_approx_eq!(
+		Vector::from_array([yaw, pitch, roll]),
+		Vector::from_array([0.3, -0.2, 1.1]),
+		epsilon = 1e-12,
+	);
+}
@@ -1,6 +1,6 @@
-use crate::{types::Stupidity, Vector};
+use crate::{num::Float, types::Stupidity, Matrix, Vector};
 use rand::{
-	distributions::{Distribution, Standard},
+	distributions::{uniform::SampleUniform, Distribution, Standard, Uniform},
 	Rng,
 };
 
@@ -12,3 +12,349 @@ where
 		Vector::build_with_fn(|_| rng.gen())
 	}
 }
+
+/// every element uniformly distributed in its own range
+/// ```
+/// use optimath::{Vector, VectorUniform};
+/// use rand::{rngs::StdRng, Rng, SeedableRng};
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let v: Vector<f64, 3> = rng.sample(VectorUniform::new(-1.0, 1.0));
+/// assert!(v.into_iter().all(|x| (-1.0..1.0).contains(&x)));
+/// ```
+pub struct VectorUniform<T: SampleUniform, const N: usize> {
+	elements: Vector<Uniform<T>, N>,
+}
+
+// derive would not bound the sampler
+impl<T: SampleUniform, const N: usize> Clone for VectorUniform<T, N>
+where
+	T: Clone,
+	T::Sampler: Clone,
+{
+	fn clone(&self) -> Self {
+		Self {
+			elements: self.elements.clone(),
+		}
+	}
+}
+
+impl<T: SampleUniform, const N: usize> core::fmt::Debug for VectorUniform<T, N>
+where
+	T: core::fmt::Debug,
+	T::Sampler: core::fmt::Debug,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("VectorUniform")
+			.field("elements", &self.elements)
+			.finish()
+	}
+}
+
+impl<T: SampleUniform + Copy, const N: usize> VectorUniform<T, N> {
+	/// the same half-open range [low, high) for all elements, panics if low >= high
+	pub fn new(low: T, high: T) -> Self {
+		Self {
+			elements: Vector::build_with_fn(|_| Uniform::new(low, high)),
+		}
+	}
+
+	/// the same closed range [low, high] for all elements, panics if low > high
+	pub fn new_inclusive(low: T, high: T) -> Self {
+		Self {
+			elements: Vector::build_with_fn(|_| Uniform::new_inclusive(low, high)),
+		}
+	}
+}
+
+impl<T: SampleUniform, const N: usize> VectorUniform<T, N> {
+	/// element i is in `[low[i], high[i])`, panics if any `low[i] >= high[i]`
+	pub fn from_bounds(low: &Vector<T, N>, high: &Vector<T, N>) -> Self {
+		Self {
+			elements: Vector::build_with_fn(|i| Uniform::new(&low[i], &high[i])),
+		}
+	}
+}
+
+impl<T: SampleUniform, const N: usize> Distribution<Vector<T, N>> for VectorUniform<T, N> {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector<T, N> {
+		Vector::build_with_fn(|i| self.elements[i].sample(rng))
+	}
+}
+
+/// independent normally distributed elements, sampled with the Box-Muller transform
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VectorNormal<T> {
+	mean: T,
+	std_dev: T,
+}
+
+impl<T: Float> VectorNormal<T> {
+	/// panics if std_dev is negative or NaN
+	pub fn new(mean: T, std_dev: T) -> Self {
+		assert!(
+			std_dev >= T::zero(),
+			"standard deviation has to be non-negative"
+		);
+		Self { mean, std_dev }
+	}
+
+	/// mean 0 and standard deviation 1
+	pub fn standard() -> Self {
+		Self::new(T::zero(), T::one())
+	}
+}
+
+impl<T: Float, const N: usize> Distribution<Vector<T, N>> for VectorNormal<T>
+where
+	Standard: Distribution<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector<T, N> {
+		let z: Vector<T, N> = standard_normal(rng);
+		Vector::build_with_fn(|i| z[i].mul_add(self.std_dev, self.mean))
+	}
+}
+
+/// two independent standard normal samples
+fn box_muller<T: Float, R: Rng + ?Sized>(rng: &mut R) -> (T, T)
+where
+	Standard: Distribution<T>,
+{
+	// gen is in [0, 1), flipping it keeps the logarithm finite
+	let u = T::one() - rng.gen::<T>();
+	let v = rng.gen::<T>();
+	let radius = (-(T::one() + T::one()) * u.ln()).sqrt();
+	let angle = T::from_f64(core::f64::consts::TAU) * v;
+	(radius * angle.cos(), radius * angle.sin())
+}
+
+fn standard_normal<T: Float, R: Rng + ?Sized, const N: usize>(rng: &mut R) -> Vector<T, N>
+where
+	Standard: Distribution<T>,
+{
+	let mut spare = None;
+	Vector::build_with_fn(|_| match spare.take() {
+		Some(z) => z,
+		None => {
+			let (z, w) = box_muller(rng);
+			spare = Some(w);
+			z
+		},
+	})
+}
+
+/// uniformly distributed directions, Vectors with a norm of 1.
+///
+/// sampling a `Vector<T, 0>` does not compile, there are no directions in zero dimensions.
+/// ```compile_fail
+/// use optimath::{UnitSphere, Vector};
+/// use rand::{rngs::StdRng, Rng, SeedableRng};
+/// let v: Vector<f32, 0> = StdRng::seed_from_u64(0).sample(UnitSphere);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnitSphere;
+
+impl<T: Float, const N: usize> Distribution<Vector<T, N>> for UnitSphere
+where
+	Standard: Distribution<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector<T, N> {
+		const { assert!(N > 0, "there are no directions in zero dimensions") };
+		// the normal distribution looks the same from every direction
+		loop {
+			let v: Vector<T, N> = standard_normal(rng);
+			let norm = v.norm();
+			if norm > T::EPSILON {
+				return Vector::build_with_fn(|i| v[i] / norm);
+			}
+		}
+	}
+}
+
+/// uniformly distributed points with a norm below 1.
+///
+/// like [UnitSphere], sampling a `Vector<T, 0>` does not compile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnitBall;
+
+impl<T: Float, const N: usize> Distribution<Vector<T, N>> for UnitBall
+where
+	Standard: Distribution<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector<T, N> {
+		const { assert!(N > 0, "the zero dimensional ball has no volume") };
+		let direction: Vector<T, N> = UnitSphere.sample(rng);
+		// the volume within radius r grows with r^N
+		let radius = rng.gen::<T>().powf(T::from_f64(1. / N as f64));
+		Vector::build_with_fn(|i| direction[i] * radius)
+	}
+}
+
+/// orthogonal matrices, uniformly distributed over all rotations and reflections (Haar measure)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HaarOrthogonal;
+
+impl<T: Float, const N: usize> Distribution<Matrix<T, N, N>> for HaarOrthogonal
+where
+	Standard: Distribution<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Matrix<T, N, N> {
+		// the Q of a gaussian matrix, Gram-Schmidt leaves the diagonal of R positive which is
+		// the sign convention that makes Q Haar distributed
+		'retry: loop {
+			let mut q: Matrix<T, N, N> = Vector::build_with_fn(|_| standard_normal(rng));
+			for column in 0..N {
+				// projecting out twice keeps the columns orthogonal to working precision
+				for _ in 0..2 {
+					for previous in 0..column {
+						let overlap = q[previous].fdot(&q[column]);
+						let rejected = Vector::build_with_fn(|row| {
+							q[column][row] - overlap * q[previous][row]
+						});
+						q[column] = rejected;
+					}
+				}
+				let norm = q[column].norm();
+				if norm <= T::EPSILON {
+					continue 'retry;
+				}
+				let normalized = Vector::build_with_fn(|row| q[column][row] / norm);
+				q[column] = normalized;
+			}
+			return q;
+		}
+	}
+}
+
+/// symmetric positive definite matrices with eigenvalues uniformly distributed in [low, high),
+/// the ratio high / low bounds the condition number
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SymmetricPositiveDefinite<T> {
+	low: T,
+	high: T,
+}
+
+impl<T: Float> SymmetricPositiveDefinite<T> {
+	/// panics unless 0 < low <= high
+	pub fn new(low: T, high: T) -> Self {
+		assert!(
+			T::zero() < low && low <= high,
+			"eigenvalues have to be in 0 < low <= high"
+		);
+		Self { low, high }
+	}
+}
+
+impl<T: Float, const N: usize> Distribution<Matrix<T, N, N>> for SymmetricPositiveDefinite<T>
+where
+	Standard: Distribution<T>,
+{
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Matrix<T, N, N> {
+		let q: Matrix<T, N, N> = HaarOrthogonal.sample(rng);
+		let spread = self.high - self.low;
+		let eigenvalues: Vector<T, N> =
+			Vector::build_with_fn(|_| spread.mul_add(rng.gen(), self.low));
+		// Q Λ Qᵀ, both triangles come from the same sums so the result is exactly symmetric
+		Matrix::from_fn(|row, column| {
+			let (row, column) = (row.min(column), row.max(column));
+			(0..N).fold(T::zero(), |acc, k| {
+				(q[k][row] * eigenvalues[k]).mul_add(q[k][column], acc)
+			})
+		})
+	}
+}
+
+#[cfg(test)]
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn uniform() {
+	let mut rng = StdRng::seed_from_u64(50);
+	let low = Vector::from_array([-1., 0., 10.]);
+	let high = Vector::from_array([1., 0.5, 20.]);
+	let bounds = VectorUniform::from_bounds(&low, &high);
+	for _ in 0..1000 {
+		let v: Vector<f64, 3> = rng.sample(&bounds);
+		assert!((0..3).all(|i| low[i] <= v[i] && v[i] < high[i]), "{:?}", v);
+		let dice: Vector<u8, 4> = rng.sample(VectorUniform::new_inclusive(1, 6));
+		assert!(dice.into_iter().all(|d| (1..=6).contains(&d)));
+	}
+}
+
+#[test]
+fn normal() {
+	let mut rng = StdRng::seed_from_u64(50);
+	let normal = VectorNormal::new(3., 2.);
+	let samples = 2000;
+	let (mut sum, mut squares) = (0., 0.);
+	for _ in 0..samples {
+		// an odd length uses half of the last pair
+		let v: Vector<f64, 7> = rng.sample(normal);
+		sum += v.into_iter().sum::<f64>();
+		squares += v.into_iter().map(|x| (x - 3.) * (x - 3.)).sum::<f64>();
+	}
+	let count = (samples * 7) as f64;
+	assert!((sum / count - 3.).abs() < 0.05, "mean {}", sum / count);
+	assert!(
+		(squares / count - 4.).abs() < 0.15,
+		"variance {}",
+		squares / count
+	);
+}
+
+#[test]
+fn sphere_and_ball() {
+	let mut rng = StdRng::seed_from_u64(50);
+	let samples = 4000;
+	let mut sum: Vector<f64, 3> = Vector::build_with_fn(|_| 0.);
+	let mut inner = 0;
+	for _ in 0..samples {
+		let direction: Vector<f64, 3> = rng.sample(UnitSphere);
+		assert!((direction.norm() - 1.).abs() < 1e-12);
+		sum = Vector::build_with_fn(|i| sum[i] + direction[i]);
+
+		let point: Vector<f32, 3> = rng.sample(UnitBall);
+		assert!(point.norm() <= 1.);
+		if point.norm() < 0.5 {
+			inner += 1;
+		}
+	}
+	assert!(sum.norm() / (samples as f64) < 0.05, "{:?}", sum);
+	// half the radius holds an eighth of the volume
+	let fraction = inner as f64 / samples as f64;
+	assert!((fraction - 0.125).abs() < 0.02, "{}", fraction);
+}
+
+#[test]
+fn orthogonal() {
+	let mut rng = StdRng::seed_from_u64(50);
+	let identity: Matrix<f64, 6, 6> = Matrix::identity();
+	let mut first = 0.;
+	for _ in 0..200 {
+		let q: Matrix<f64, 6, 6> = rng.sample(HaarOrthogonal);
+		let qtq: Matrix<f64, 6, 6> = Matrix::from_fn(|row, column| q[row].fdot(&q[column]));
+		crate::assert_approx_eq!(qtq, identity, epsilon = 1e-12);
+		first += q[0][0];
+	}
+	// uniform over all orthogonal matrices, so no direction is preferred
+	assert!((first / 200.).abs() < 0.1, "{}", first);
+
+	let q: Matrix<f32, 16, 16> = rng.sample(HaarOrthogonal);
+	let qtq: Matrix<f32, 16, 16> = Matrix::from_fn(|row, column| q[row].fdot(&q[column]));
+	crate::assert_approx_eq!(qtq, Matrix::identity(), epsilon = 1e-5);
+}
+
+#[test]
+fn positive_definite() {
+	let mut rng = StdRng::seed_from_u64(50);
+	let spd = SymmetricPositiveDefinite::new(0.5, 4.);
+	for _ in 0..100 {
+		let a: Matrix<f64, 5, 5> = rng.sample(spd);
+		assert!((0..5).all(|i| (0..5).all(|j| a[i][j] == a[j][i])));
+		// the rayleigh quotient stays between the smallest and largest eigenvalue
+		let x: Vector<f64, 5> = rng.sample(VectorNormal::standard());
+		let ax: Vector<f64, 5> =
+			Vector::build_with_fn(|row| (0..5).map(|k| a[k][row] * x[k]).sum());
+		let rayleigh = x.fdot(&ax) / x.fdot(&x);
+		assert!((0.5 - 1e-12..=4. + 1e-12).contains(&rayleigh), "{}", rayleigh);
+	}
+}
@@ -0,0 +1,406 @@
+//! other serde representations of a Matrix, for use with `#[serde(with = "...")]`.
+//!
+//! the Serialize impl of Vector writes a tuple, so a Matrix becomes a tuple of its columns. these
+//! modules write it differently:
+//!
+//! * [flat_row_major]: one array of M * N elements, row after row
+//! * [flat_column_major]: one array of M * N elements, column after column, like in memory
+//! * [nested]: an array of M rows with N elements each
+//! * [shaped]: `{ "shape": [M, N], "data": [...] }` with the data row major, the shape is checked
+//!   against M and N when deserializing
+//!
+//! all of them are tuples to serde, so compact formats like bincode do not store any lengths.
+//! ```
+//! use optimath::Matrix;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Camera {
+//!     #[serde(with = "optimath::repr::shaped")]
+//!     projection: Matrix<f32, 2, 3>,
+//! }
+//!
+//! let camera = Camera { projection: Matrix::from_fn(|row, column| (row * 3 + column) as f32) };
+//! let json = serde_json::to_string(&camera).unwrap();
+//! assert_eq!(json, r#"{"projection":{"shape":[2,3],"data":[0.0,1.0,2.0,3.0,4.0,5.0]}}"#);
+//! assert_eq!(serde_json::from_str::<Camera>(&json).unwrap(), camera);
+//!
+//! let wrong = r#"{"projection":{"shape":[3,2],"data":[0.0,1.0,2.0,3.0,4.0,5.0]}}"#;
+//! assert!(serde_json::from_str::<Camera>(wrong).is_err());
+//! ```
+use crate::{serialize::try_build, types::Matrix};
+use core::{fmt, marker::PhantomData};
+use serde::{
+	de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+	ser::{Serialize, SerializeStruct, SerializeTuple, Serializer},
+};
+
+/// the elements in either order as one flat tuple
+struct Flat<'a, T, const M: usize, const N: usize> {
+	matrix: &'a Matrix<T, M, N>,
+	row_major: bool,
+}
+
+impl<'a, T: Serialize, const M: usize, const N: usize> Serialize for Flat<'a, T, M, N> {
+	fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+		let mut tuple = s.serialize_tuple(M * N)?;
+		if self.row_major {
+			for row in 0..M {
+				for column in 0..N {
+					tuple.serialize_element(&self.matrix[column][row])?;
+				}
+			}
+		} else {
+			for element in self.matrix.into_iter().flatten() {
+				tuple.serialize_element(element)?;
+			}
+		}
+		tuple.end()
+	}
+}
+
+struct FlatVisitor<T, const M: usize, const N: usize> {
+	row_major: bool,
+	element: PhantomData<T>,
+}
+
+impl<'de, T, const M: usize, const N: usize> Visitor<'de> for FlatVisitor<T, M, N>
+where
+	T: Deserialize<'de>,
+{
+	type Value = Matrix<T, M, N>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a sequence of {} elements", M * N)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut elements: Matrix<Option<T>, M, N> = Matrix::from_fn(|_, _| None);
+		for i in 0..M * N {
+			let (row, column) = if self.row_major { (i / N, i % N) } else { (i % M, i / M) };
+			let element = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+			elements[column][row] = Some(element);
+		}
+		if seq.next_element::<de::IgnoredAny>()?.is_some() {
+			return Err(de::Error::invalid_length(M * N + 1, &self));
+		}
+		try_build(|column| try_build(|row| Ok(elements[column][row].take().unwrap())))
+	}
+}
+
+fn deserialize_flat<'de, D, T, const M: usize, const N: usize>(
+	d: D,
+	row_major: bool,
+) -> Result<Matrix<T, M, N>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de>,
+{
+	d.deserialize_tuple(M * N, FlatVisitor { row_major, element: PhantomData })
+}
+
+/// `[a11, a12, ..., a1N, a21, ..., aMN]`
+pub mod flat_row_major {
+	use super::*;
+
+	pub fn serialize<S, T, const M: usize, const N: usize>(
+		matrix: &Matrix<T, M, N>,
+		s: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		T: Serialize,
+	{
+		Flat { matrix, row_major: true }.serialize(s)
+	}
+
+	pub fn deserialize<'de, D, T, const M: usize, const N: usize>(
+		d: D,
+	) -> Result<Matrix<T, M, N>, D::Error>
+	where
+		D: Deserializer<'de>,
+		T: Deserialize<'de>,
+	{
+		deserialize_flat(d, true)
+	}
+}
+
+/// `[a11, a21, ..., aM1, a12, ..., aMN]`
+pub mod flat_column_major {
+	use super::*;
+
+	pub fn serialize<S, T, const M: usize, const N: usize>(
+		matrix: &Matrix<T, M, N>,
+		s: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		T: Serialize,
+	{
+		Flat { matrix, row_major: false }.serialize(s)
+	}
+
+	pub fn deserialize<'de, D, T, const M: usize, const N: usize>(
+		d: D,
+	) -> Result<Matrix<T, M, N>, D::Error>
+	where
+		D: Deserializer<'de>,
+		T: Deserialize<'de>,
+	{
+		deserialize_flat(d, false)
+	}
+}
+
+/// `[[a11, ..., a1N], ..., [aM1, ..., aMN]]`
+pub mod nested {
+	use super::*;
+	use crate::types::Vector;
+
+	struct Row<'a, T, const M: usize, const N: usize>(&'a Matrix<T, M, N>, usize);
+
+	impl<'a, T: Serialize, const M: usize, const N: usize> Serialize for Row<'a, T, M, N> {
+		fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+			let mut tuple = s.serialize_tuple(N)?;
+			for column in self.0 {
+				tuple.serialize_element(&column[self.1])?;
+			}
+			tuple.end()
+		}
+	}
+
+	pub fn serialize<S, T, const M: usize, const N: usize>(
+		matrix: &Matrix<T, M, N>,
+		s: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		T: Serialize,
+	{
+		let mut tuple = s.serialize_tuple(M)?;
+		for row in 0..M {
+			tuple.serialize_element(&Row(matrix, row))?;
+		}
+		tuple.end()
+	}
+
+	pub fn deserialize<'de, D, T, const M: usize, const N: usize>(
+		d: D,
+	) -> Result<Matrix<T, M, N>, D::Error>
+	where
+		D: Deserializer<'de>,
+		T: Deserialize<'de>,
+	{
+		let rows = Vector::<Vector<T, N>, M>::deserialize(d)?;
+		let mut rows: Vector<Vector<Option<T>, N>, M> =
+			rows.into_iter().map(|row| row.into_iter().map(Some).collect()).collect();
+		try_build(|column| try_build(|row| Ok(rows[row][column].take().unwrap())))
+	}
+}
+
+/// `{ "shape": [M, N], "data": [a11, a12, ..., aMN] }`
+pub mod shaped {
+	use super::*;
+
+	const FIELDS: &[&str] = &["shape", "data"];
+
+	pub fn serialize<S, T, const M: usize, const N: usize>(
+		matrix: &Matrix<T, M, N>,
+		s: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		T: Serialize,
+	{
+		let mut object = s.serialize_struct("Matrix", 2)?;
+		object.serialize_field("shape", &[M, N])?;
+		object.serialize_field("data", &Flat { matrix, row_major: true })?;
+		object.end()
+	}
+
+	/// reads a shape of any length, and fails unless it is [M, N]
+	struct Shape<const M: usize, const N: usize>;
+
+	impl<'de, const M: usize, const N: usize> Deserialize<'de> for Shape<M, N> {
+		fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+			d.deserialize_tuple(2, Shape)
+		}
+	}
+
+	impl<'de, const M: usize, const N: usize> Visitor<'de> for Shape<M, N> {
+		type Value = Shape<M, N>;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "the shape [{}, {}]", M, N)
+		}
+
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let rows = seq.next_element::<usize>()?;
+			let columns = seq.next_element::<usize>()?;
+			match (rows, columns, seq.next_element::<usize>()?) {
+				(Some(rows), Some(columns), None) if (rows, columns) == (M, N) => Ok(Shape),
+				(Some(rows), Some(columns), None) => Err(de::Error::custom(format_args!(
+					"shape mismatch: expected [{}, {}] but found [{}, {}]",
+					M, N, rows, columns
+				))),
+				_ => Err(de::Error::custom(format_args!(
+					"shape mismatch: expected [{}, {}] but found a different rank",
+					M, N
+				))),
+			}
+		}
+	}
+
+	/// the data array as a field
+	struct Data<T, const M: usize, const N: usize>(Matrix<T, M, N>);
+
+	impl<'de, T: Deserialize<'de>, const M: usize, const N: usize> Deserialize<'de>
+		for Data<T, M, N>
+	{
+		fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+			deserialize_flat(d, true).map(Data)
+		}
+	}
+
+	enum Field {
+		Shape,
+		Data,
+	}
+
+	impl<'de> Deserialize<'de> for Field {
+		fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+			struct FieldVisitor;
+			impl<'de> Visitor<'de> for FieldVisitor {
+				type Value = Field;
+				fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					f.write_str("`shape` or `data`")
+				}
+				fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+					match value {
+						"shape" => Ok(Field::Shape),
+						"data" => Ok(Field::Data),
+						_ => Err(de::Error::unknown_field(value, FIELDS)),
+					}
+				}
+			}
+			d.deserialize_identifier(FieldVisitor)
+		}
+	}
+
+	struct ShapedVisitor<T, const M: usize, const N: usize>(PhantomData<T>);
+
+	impl<'de, T, const M: usize, const N: usize> Visitor<'de> for ShapedVisitor<T, M, N>
+	where
+		T: Deserialize<'de>,
+	{
+		type Value = Matrix<T, M, N>;
+
+		fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "a {}x{} matrix with shape and data", M, N)
+		}
+
+		fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+			let missing = |i| de::Error::invalid_length(i, &self);
+			seq.next_element::<Shape<M, N>>()?.ok_or_else(|| missing(0))?;
+			let data = seq.next_element::<Data<T, M, N>>()?.ok_or_else(|| missing(1))?;
+			Ok(data.0)
+		}
+
+		fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+			let (mut shape, mut data) = (None, None);
+			while let Some(key) = map.next_key()? {
+				match key {
+					Field::Shape if shape.is_some() => {
+						return Err(de::Error::duplicate_field("shape"))
+					},
+					Field::Shape => shape = Some(map.next_value::<Shape<M, N>>()?),
+					Field::Data if data.is_some() => return Err(de::Error::duplicate_field("data")),
+					Field::Data => data = Some(map.next_value::<Data<T, M, N>>()?),
+				}
+			}
+			shape.ok_or_else(|| de::Error::missing_field("shape"))?;
+			Ok(data.ok_or_else(|| de::Error::missing_field("data"))?.0)
+		}
+	}
+
+	pub fn deserialize<'de, D, T, const M: usize, const N: usize>(
+		d: D,
+	) -> Result<Matrix<T, M, N>, D::Error>
+	where
+		D: Deserializer<'de>,
+		T: Deserialize<'de>,
+	{
+		d.deserialize_struct("Matrix", FIELDS, ShapedVisitor(PhantomData))
+	}
+}
+
+#[cfg(test)]
+mod test_types {
+	use crate::Matrix;
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Serialize, Deserialize, PartialEq, Debug)]
+	pub struct All {
+		#[serde(with = "crate::repr::flat_row_major")]
+		pub row_major: Matrix<i32, 2, 3>,
+		#[serde(with = "crate::repr::flat_column_major")]
+		pub column_major: Matrix<i32, 2, 3>,
+		#[serde(with = "crate::repr::nested")]
+		pub nested: Matrix<i32, 2, 3>,
+		#[serde(with = "crate::repr::shaped")]
+		pub shaped: Matrix<i32, 2, 3>,
+	}
+}
+
+#[test]
+fn human_readable() {
+	use test_types::All;
+	let m = Matrix::from_fn(|row, column| (10 * row + column) as i32);
+	let all = All { row_major: m, column_major: m, nested: m, shaped: m };
+	let json = serde_json::to_string(&all).unwrap();
+	let expected = concat!(
+		r#"{"row_major":[0,1,2,10,11,12],"column_major":[0,10,1,11,2,12],"#,
+		r#""nested":[[0,1,2],[10,11,12]],"shaped":{"shape":[2,3],"data":[0,1,2,10,11,12]}}"#,
+	);
+	assert_eq!(json, expected);
+	assert_eq!(serde_json::from_str::<All>(&json).unwrap(), all);
+
+	// fields in any order
+	let shaped = r#"{"data":[0,1,2,10,11,12],"shape":[2,3]}"#;
+	let parsed: Matrix<i32, 2, 3> =
+		shaped::deserialize(&mut serde_json::Deserializer::from_str(shaped)).unwrap();
+	assert_eq!(parsed, m);
+}
+
+#[test]
+fn invalid() {
+	extern crate std;
+	use std::string::ToString;
+	let parse = |json: &str| {
+		shaped::deserialize::<_, i32, 2, 3>(&mut serde_json::Deserializer::from_str(json))
+			.unwrap_err()
+			.to_string()
+	};
+	assert!(parse(r#"{"shape":[3,2],"data":[0,1,2,3,4,5]}"#)
+		.starts_with("shape mismatch: expected [2, 3] but found [3, 2]"));
+	assert!(parse(r#"{"shape":[6],"data":[0,1,2,3,4,5]}"#).contains("different rank"));
+	assert!(parse(r#"{"shape":[2,3],"data":[0,1,2,3,4]}"#).starts_with("invalid length 5"));
+	assert!(parse(r#"{"shape":[2,3],"data":[0,1,2,3,4,5,6]}"#).starts_with("invalid length 7"));
+	assert!(parse(r#"{"data":[0,1,2,3,4,5]}"#).starts_with("missing field `shape`"));
+
+	let short_row = r#"[[0,1,2],[3,4]]"#;
+	let mut json = serde_json::Deserializer::from_str(short_row);
+	assert!(nested::deserialize::<_, i32, 2, 3>(&mut json).is_err());
+}
+
+#[test]
+fn compact() {
+	use test_types::All;
+	let m = Matrix::from_fn(|row, column| (10 * row + column) as i32);
+	let all = All { row_major: m, column_major: m, nested: m, shaped: m };
+	let bytes = bincode::serialize(&all).unwrap();
+	// no lengths anywhere, just the shape of the last one
+	assert_eq!(bytes.len(), 4 * 6 * 4 + 2 * 8);
+	assert_eq!(bytes[..8], [0, 0, 0, 0, 1, 0, 0, 0]);
+	assert_eq!(bincode::deserialize::<All>(&bytes).unwrap(), all);
+	assert!(bincode::deserialize::<All>(&bytes[..bytes.len() - 1]).is_err());
+}
@@ -0,0 +1,174 @@
+//! a list of unknown length where every entry has a compile time known number of features
+//!
+//! this is the U x S(F) shape from the ideas section, for example a table of samples where the
+//! number of samples is only known after loading them.
+use crate::types::{Matrix, Stupidity, Vector};
+use alloc::vec::Vec;
+use core::{
+	iter::{FromIterator, Sum},
+	ops::{Index, IndexMut, Mul},
+};
+
+/// a runtime sized list of rows with F features each
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Rows<T, const F: usize> {
+	pub(crate) inner: Vec<Vector<T, F>>,
+}
+
+impl<T, const F: usize> Rows<T, F> {
+	pub fn new() -> Self { Rows { inner: Vec::new() } }
+	pub fn push(&mut self, row: Vector<T, F>) { self.inner.push(row) }
+
+	pub fn len(&self) -> usize { self.inner.len() }
+	pub fn is_empty(&self) -> bool { self.inner.is_empty() }
+
+	pub fn as_slice(&self) -> &[Vector<T, F>] { &self.inner }
+	pub fn into_vec(self) -> Vec<Vector<T, F>> { self.inner }
+
+	pub fn iter(&self) -> core::slice::Iter<'_, Vector<T, F>> { self.inner.iter() }
+}
+
+impl<T, const F: usize> Rows<T, F>
+where
+	for<'x> &'x T: Mul<&'x T, Output = T>,
+	T: Sum,
+{
+	/// U x S(F) * S(F) x S(K) = U x S(K)
+	pub fn matrix_multiply<const K: usize>(&self, other: &Matrix<T, F, K>) -> Rows<T, K> {
+		self.iter()
+			.map(|row| Vector::build_with_fn(|k| other[k].dot(row)))
+			.collect()
+	}
+
+	/// the gram matrix Xᵀ X, i.e. S(F) x U * U x S(F) = S(F) x S(F)
+	pub fn gram(&self) -> Matrix<T, F, F> {
+		Matrix::build_with_fn(|j| {
+			Vector::build_with_fn(|i| self.iter().map(|row| &row[i] * &row[j]).sum())
+		})
+	}
+}
+
+impl<'a, T: 'a + Sum<&'a T>, const F: usize> Rows<T, F> {
+	pub fn feature_sum(&'a self) -> Vector<T, F> {
+		Vector::build_with_fn(|f| self.iter().map(|row| &row[f]).sum())
+	}
+}
+
+impl<T: PartialOrd + Copy, const F: usize> Rows<T, F> {
+	/// None if there are no rows
+	pub fn feature_min(&self) -> Option<Vector<T, F>> {
+		self.feature_fold(|acc, e| if e < acc { e } else { acc })
+	}
+
+	/// None if there are no rows
+	pub fn feature_max(&self) -> Option<Vector<T, F>> {
+		self.feature_fold(|acc, e| if e > acc { e } else { acc })
+	}
+
+	fn feature_fold(&self, f: impl Fn(T, T) -> T) -> Option<Vector<T, F>> {
+		let (first, rest) = self.inner.split_first()?;
+		let mut acc = *first;
+		for row in rest {
+			for (a, e) in acc.inner.iter_mut().zip(row) {
+				*a = f(*a, *e);
+			}
+		}
+		Some(acc)
+	}
+}
+
+macro_rules! float_stats {
+	( $t:ty ) => {
+		impl<const F: usize> Rows<$t, F> {
+			/// None if there are no rows
+			pub fn feature_mean(&self) -> Option<Vector<$t, F>> {
+				if self.is_empty() {
+					return None;
+				}
+				let len = self.len() as $t;
+				Some(Vector::build_with_fn(|f| {
+					self.iter().map(|row| row[f]).sum::<$t>() / len
+				}))
+			}
+
+			/// population variance of each feature. None if there are no rows
+			pub fn feature_variance(&self) -> Option<Vector<$t, F>> {
+				let mean = self.feature_mean()?;
+				let len = self.len() as $t;
+				Some(Vector::build_with_fn(|f| {
+					self.iter()
+						.map(|row| (row[f] - mean[f]) * (row[f] - mean[f]))
+						.sum::<$t>() / len
+				}))
+			}
+		}
+	};
+}
+
+float_stats!(f32);
+float_stats!(f64);
+
+impl<T, const F: usize> From<Vec<Vector<T, F>>> for Rows<T, F> {
+	fn from(inner: Vec<Vector<T, F>>) -> Self { Rows { inner } }
+}
+
+impl<T, const F: usize> FromIterator<Vector<T, F>> for Rows<T, F> {
+	fn from_iter<I: IntoIterator<Item = Vector<T, F>>>(iter: I) -> Self {
+		Rows {
+			inner: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl<T, const F: usize> IntoIterator for Rows<T, F> {
+	type Item = Vector<T, F>;
+	type IntoIter = alloc::vec::IntoIter<Vector<T, F>>;
+
+	fn into_iter(self) -> Self::IntoIter { self.inner.into_iter() }
+}
+
+impl<'a, T, const F: usize> IntoIterator for &'a Rows<T, F> {
+	type Item = &'a Vector<T, F>;
+	type IntoIter = core::slice::Iter<'a, Vector<T, F>>;
+
+	fn into_iter(self) -> Self::IntoIter { self.inner.iter() }
+}
+
+impl<T, const F: usize> Index<usize> for Rows<T, F> {
+	type Output = Vector<T, F>;
+	fn index(&self, index: usize) -> &Vector<T, F> { &self.inner[index] }
+}
+
+impl<T, const F: usize> IndexMut<usize> for Rows<T, F> {
+	fn index_mut(&mut self, index: usize) -> &mut Vector<T, F> { &mut self.inner[index] }
+}
+
+#[test]
+fn rows_maths() {
+	let rows: Rows<f32, 2> = (0..5)
+		.map(|i| Vector::build_with_fn(|f| (i * (f + 1)) as f32))
+		.collect();
+
+	// swaps the features and doubles the second one
+	let w: Matrix<f32, 2, 2> = (0..2)
+		.map(|k| Vector::build_with_fn(|f| if f == k { 0. } else { (k + 1) as f32 }))
+		.collect();
+	let projected: Rows<f32, 2> = rows.matrix_multiply(&w);
+	for (r, p) in rows.iter().zip(&projected) {
+		assert_eq!(p[0], r[1]);
+		assert_eq!(p[1], 2. * r[0]);
+	}
+
+	let gram = rows.gram();
+	assert_eq!(gram[0][0], 30.);
+	assert_eq!(gram[1][0], 60.);
+	assert_eq!(gram[0][1], 60.);
+	assert_eq!(gram[1][1], 120.);
+
+	assert_eq!(rows.feature_sum()[1], 20.);
+	assert_eq!(rows.feature_mean().unwrap()[1], 4.);
+	assert_eq!(rows.feature_variance().unwrap()[0], 2.);
+	assert_eq!(rows.feature_min().unwrap()[1], 0.);
+	assert_eq!(rows.feature_max().unwrap()[1], 8.);
+	assert_eq!(Rows::<f32, 2>::new().feature_mean(), None);
+}
@@ -15,7 +15,7 @@ where
 }
 
 use core::fmt;
-use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
 
 impl<'de, T, const N: usize> Deserialize<'de> for Vector<T, N>
 where
@@ -38,14 +38,23 @@ impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for ElementVisitor<T
 	where
 		A: SeqAccess<'de>,
 	{
-		//fixme: fail softly/by returning a Result
-		use crate::types::Stupidity;
-		Ok(Vector::<T, N>::build_with_fn(|_| {
-			seq.next_element().unwrap().unwrap()
-		}))
+		try_build(|i| seq.next_element()?.ok_or_else(|| Error::invalid_length(i, &self)))
 	}
 }
 
+/// like build_with_fn but stops at the first error
+pub(crate) fn try_build<T, E, const N: usize>(
+	mut f: impl FnMut(usize) -> Result<T, E>,
+) -> Result<Vector<T, N>, E> {
+	use crate::types::Stupidity;
+	let mut elements: Vector<Option<T>, N> = Vector::build_with_fn(|_| None);
+	for (i, element) in elements.inner.iter_mut().enumerate() {
+		*element = Some(f(i)?);
+	}
+	// every element has been set
+	Ok(Vector::build_with_fn(|i| elements[i].take().unwrap()))
+}
+
 #[test]
 fn ser_de_test() {
 	use crate::Matrix;
@@ -60,3 +69,10 @@ fn ser_de_test() {
 	let decoded = bincode::deserialize(&buf[..]).unwrap();
 	assert_eq!(matrix, decoded);
 }
+
+#[test]
+fn short_input() {
+	let buf = [0u8; 3 * 4];
+	let short: Result<crate::Matrix<u32, 2, 2>, _> = bincode::deserialize(&buf[..]);
+	assert!(short.is_err());
+}
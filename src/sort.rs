@@ -0,0 +1,150 @@
+//! sorting the elements of a Vector without allocating.
+//!
+//! the stable sorts merge indices in a second Vector on the stack and then move the elements in
+//! place, so T does not have to be Copy or Clone.
+use crate::types::{Stupidity, Vector};
+use core::cmp::Ordering;
+
+impl<T, const N: usize> Vector<T, N> {
+	/// the indices that would sort self, equal elements keep their order
+	pub fn argsort_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Vector<usize, N> {
+		let mut order = Vector::build_with_fn(|i| i);
+		let mut buffer = order;
+		// bottom up merge sort, runs of width elements are merged into runs of 2 * width
+		let mut width = 1;
+		while width < N {
+			for start in (0..N).step_by(2 * width) {
+				let middle = (start + width).min(N);
+				let end = (start + 2 * width).min(N);
+				let (mut left, mut right) = (start, middle);
+				for slot in &mut buffer.inner[start..end] {
+					// only strictly smaller elements from the right run go first, for stability
+					let take_right = left == middle
+						|| (right < end
+							&& compare(&self[order[right]], &self[order[left]]).is_lt());
+					if take_right {
+						*slot = order[right];
+						right += 1;
+					} else {
+						*slot = order[left];
+						left += 1;
+					}
+				}
+			}
+			core::mem::swap(&mut order, &mut buffer);
+			width *= 2;
+		}
+		order
+	}
+
+	/// stable sort with a comparison function
+	pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+		let order = self.argsort_by(compare);
+		self.permute(&order);
+	}
+
+	/// reorders self so that the new `self[i]` is the old `self[order[i]]`.
+	///
+	/// panics if order is not a permutation of 0..N.
+	pub fn permute(&mut self, order: &Vector<usize, N>) {
+		let mut seen = [false; N];
+		for &i in order {
+			assert!(i < N && !seen[i], "{:?} is not a permutation", order);
+			seen[i] = true;
+		}
+		for i in 0..N {
+			// the element that belongs here was swapped further back if it came from before i
+			let mut source = order[i];
+			while source < i {
+				source = order[source];
+			}
+			self.inner.swap(i, source);
+		}
+	}
+}
+
+impl<T: Ord, const N: usize> Vector<T, N> {
+	/// stable sort, equal elements keep their order
+	pub fn sort(&mut self) { self.sort_by(Ord::cmp) }
+
+	/// faster than sort but may reorder equal elements
+	pub fn sort_unstable(&mut self) { self.inner.sort_unstable() }
+
+	/// the indices that would sort self, equal elements keep their order
+	pub fn argsort(&self) -> Vector<usize, N> { self.argsort_by(Ord::cmp) }
+}
+
+#[test]
+fn sorting() {
+	let mut v = Vector::from_array([5, 3, 9, 1, 3, 7, 0]);
+	assert_eq!(v.argsort(), Vector::from_array([6, 3, 1, 4, 0, 5, 2]));
+	let mut w = v;
+	v.sort();
+	w.sort_unstable();
+	assert_eq!(v, Vector::from_array([0, 1, 3, 3, 5, 7, 9]));
+	assert_eq!(v, w);
+
+	// stable: the pairs with equal keys stay in their original order
+	let mut pairs = Vector::from_array([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')]);
+	pairs.sort_by(|a, b| a.0.cmp(&b.0));
+	let letters: Vector<char, 5> = pairs.into_iter().map(|p| p.1).collect();
+	assert_eq!(letters, Vector::from_array(['e', 'b', 'd', 'a', 'c']));
+
+	let mut floats = Vector::from_array([0.5, -1., 2.]);
+	floats.sort_by(|a, b| b.partial_cmp(a).unwrap());
+	assert_eq!(floats, Vector::from_array([2., 0.5, -1.]));
+
+	let mut empty: Vector<i32, 0> = Vector::from_array([]);
+	empty.sort();
+}
+
+#[test]
+fn permute() {
+	let order = Vector::from_array([2, 0, 3, 1, 4]);
+	let mut v = Vector::from_array(['a', 'b', 'c', 'd', 'e']);
+	v.permute(&order);
+	assert_eq!(v, Vector::from_array(['c', 'a', 'd', 'b', 'e']));
+	let mut v = Vector::from_array(['a', 'b', 'c', 'd', 'e']);
+	v.permute(&Vector::from_array([4, 3, 2, 1, 0]));
+	assert_eq!(v, Vector::from_array(['e', 'd', 'c', 'b', 'a']));
+}
+
+#[test]
+#[should_panic]
+fn permute_duplicate() { Vector::from_array([1, 2, 3]).permute(&Vector::from_array([0, 0, 1])) }
+
+#[test]
+fn ordering() {
+	use core::hash::{Hash, Hasher};
+	let a = Vector::from_array([1, 2, 3]);
+	let b = Vector::from_array([1, 3, 0]);
+	assert!(a < b);
+	assert_eq!(a.cmp(&b), Ordering::Less);
+	assert_eq!(a.max(b), b);
+	// element-wise lt is an inherent method, the lexicographic one needs the trait
+	assert!(PartialOrd::lt(&a, &b));
+	assert!(!a.lt(&b).all());
+	// the same for clamp, which Ord has too
+	assert_eq!(b.clamp(1, 2), Vector::from_array([1, 2, 1]));
+	assert_eq!(Ord::clamp(b, a, a), a);
+	assert_eq!(Vector::from_array([f32::NAN]).partial_cmp(&Vector::from_array([0.])), None);
+
+	// core has no hasher, this is just deterministic
+	#[derive(Default)]
+	struct Polynomial(u64);
+	impl Hasher for Polynomial {
+		fn finish(&self) -> u64 { self.0 }
+		fn write(&mut self, bytes: &[u8]) {
+			for (i, b) in bytes.iter().enumerate() {
+				self.0 = self.0.wrapping_mul(31).wrapping_add(*b as u64 + i as u64);
+			}
+		}
+	}
+	let hash = |h: &dyn Fn(&mut Polynomial)| {
+		let mut hasher = Polynomial::default();
+		h(&mut hasher);
+		hasher.finish()
+	};
+	assert_eq!(hash(&|h| a.hash(h)), hash(&|h| a.as_slice().hash(h)));
+	assert_ne!(hash(&|h| a.hash(h)), hash(&|h| b.hash(h)));
+}
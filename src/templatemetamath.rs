@@ -8,125 +8,523 @@
 //! instead of all elements from one (two).
 //! cache locality will be slightly mitigated, as operations will (soon (TM)) run on multiple data
 //! at once.
+//!
+//! every node of a calculation ([VAdd], [VSub], [VMul], [VDiv], [VNeg] and [VMap]) implements
+//! [VExpr] and can be combined with any other [VExpr] using the standard operators. &Vector,
+//! VectorView, VectorRef and scalars are the leaves of the calculation.
+//!
+//! matrix products ([VMatMul], [VMatVec]) and transposes ([VTranspose]) are nodes too, so only
+//! the columns (or rows) that are actually needed get calculated.
+//!
+//! calculations are started by calling .lazy() on a Vector (or wrapping any leaf in [V]):
+//!
+//!     use optimath::Vector;
+//!     let a = Vector::from([0f32, 3., 8.]);
+//!     let b = Vector::from([1f32, 3., 9.]);
+//!
+//!     // nothing has been calculated yet
+//!     let calculation = ((a.lazy() + &b) * 2f32 - &b).map(f32::sqrt);
+//!     // now everything is calculated in one go
+//!     let result: Vector<f32, 3> = calculation.realize();
+//!     assert_eq!(result, Vector::from([1., 3., 5.]));
 
-//todo: add V type that wraps a Vector and implements virtual add/mul/sub/div, just at type to get
-//your calculation started
+use crate::{
+	consts::ConstIndex,
+	types::{Matrix, Stupidity, Vector},
+	view::{TransposedMatrixView, VectorRef, VectorView},
+};
+use core::{iter::Sum, marker::PhantomData, ops::*};
 
-//todo: add transpose/matrix multiplication, potentially switch from basing this off off ConstIndex
-//to ConstIter
+/// a calculation that can be evaluated element by element.
+///
+/// this is [ConstIndex] with the element type as an associated type instead of a generic
+/// parameter. that way a node knows its element type and arbitrary nodes can be combined without
+/// running into "unconstrained type parameter" errors.
+pub trait VExpr<const N: usize>: Copy {
+	type Item;
+	fn at(self, index: usize) -> Self::Item;
+}
 
-use crate::consts::{ConstIndex, ConstIterator};
-use core::ops::*;
+impl<'a, T, const N: usize> VExpr<N> for &'a Vector<T, N> {
+	type Item = &'a T;
+	#[inline]
+	fn at(self, index: usize) -> &'a T { &self.inner[index] }
+}
 
-pub struct VAdd<T, L, R, LT, RT, const N: usize>
-where
-	// use Borrow maybe?
-	// hard to abstract over lifetimes + owned/borrowd
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
-	LT: Add<RT, Output = T>,
-{
+// copies the whole Vector around, lets just hope for the optimizer. borrowing is preferable.
+impl<T: Copy, const N: usize> VExpr<N> for Vector<T, N> {
+	type Item = T;
+	#[inline]
+	fn at(self, index: usize) -> T { self.inner[index] }
+}
+
+impl<'a, T, const M: usize, const N: usize> VExpr<M> for VectorView<'a, T, M, N> {
+	type Item = &'a T;
+	#[inline]
+	fn at(self, index: usize) -> &'a T { self.i(index) }
+}
+
+impl<'a, T, const N: usize> VExpr<N> for VectorRef<'a, T, N> {
+	type Item = &'a T;
+	#[inline]
+	fn at(self, index: usize) -> &'a T { &self.inner[index] }
+}
+
+/// the elements are the rows of the matrix
+impl<'a, T, const M: usize, const N: usize> VExpr<N> for TransposedMatrixView<'a, T, M, N> {
+	type Item = VectorView<'a, T, M, N>;
+	#[inline]
+	fn at(self, index: usize) -> VectorView<'a, T, M, N> { self.i(index) }
+}
+
+// scalars are the same at every index
+macro_rules! scalar_leaf {
+	( $( $t:ty ), * ) => {
+		$(
+			impl<const N: usize> VExpr<N> for $t {
+				type Item = $t;
+				#[inline]
+				fn at(self, _index: usize) -> $t { self }
+			}
+		)*
+	};
+}
+
+scalar_leaf!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+macro_rules! binary_node {
+	( $name:ident, $op:tt, $fn:ident ) => {
+		#[derive(Copy, Clone, Debug)]
+		pub struct $name<L, R, const N: usize> {
+			l: L,
+			r: R,
+		}
+
+		impl<L, R, const N: usize> $name<L, R, N>
+		where
+			L: VExpr<N>,
+			R: VExpr<N>,
+			L::Item: $op<R::Item>,
+		{
+			pub fn new(l: L, r: R) -> Self { $name { l, r } }
+		}
+
+		impl<L, R, const N: usize> VExpr<N> for $name<L, R, N>
+		where
+			L: VExpr<N>,
+			R: VExpr<N>,
+			L::Item: $op<R::Item>,
+		{
+			type Item = <L::Item as $op<R::Item>>::Output;
+			#[inline]
+			fn at(self, index: usize) -> Self::Item {
+				let l = self.l.at(index);
+				let r = self.r.at(index);
+				$op::$fn(l, r)
+			}
+		}
+
+		node!({ L, R, const N: usize } $name<L, R, N>, N);
+	};
+}
+
+// implements the operators and the calculation-wide methods for a node
+macro_rules! node {
+	( { $( $generics:tt )* } $name:ty, $n:ident $( where $( $bounds:tt )* )? ) => {
+		node_op!({ $( $generics )* } $name, $n, Add, add, VAdd<
+			<<Self as VExpr<$n>>::Item as Add<Rhs::Item>>::Output,
+			Self,
+			Rhs,
+			<Self as VExpr<$n>>::Item,
+			Rhs::Item,
+			$n,
+		>; $( $( $bounds )* )?);
+		node_op!({ $( $generics )* } $name, $n, Sub, sub, VSub<Self, Rhs, $n>; $( $( $bounds )* )?);
+		node_op!({ $( $generics )* } $name, $n, Mul, mul, VMul<Self, Rhs, $n>; $( $( $bounds )* )?);
+		node_op!({ $( $generics )* } $name, $n, Div, div, VDiv<Self, Rhs, $n>; $( $( $bounds )* )?);
+
+		impl<$( $generics )*> Neg for $name
+		where
+			Self: VExpr<$n>,
+			$( $( $bounds )* )?
+			<Self as VExpr<$n>>::Item: Neg,
+		{
+			type Output = VNeg<Self, $n>;
+			fn neg(self) -> VNeg<Self, $n> { VNeg::new(self) }
+		}
+
+		// this is safe because every index is evaluated independently
+		unsafe impl<$( $generics )*> ConstIndex<<Self as VExpr<$n>>::Item, $n> for $name
+		where
+			Self: VExpr<$n>,
+			$( $( $bounds )* )?
+		{
+			#[inline]
+			fn i(self, index: usize) -> <Self as VExpr<$n>>::Item { self.at(index) }
+		}
+
+		impl<$( $generics )*> $name
+		where
+			Self: VExpr<$n>,
+			$( $( $bounds )* )?
+		{
+			/// applies f to every element of the result
+			pub fn map<Out, Func>(self, f: Func) -> VMap<Self, Func, $n>
+			where
+				Func: Fn(<Self as VExpr<$n>>::Item) -> Out + Copy,
+			{
+				VMap::new(self, f)
+			}
+
+			/// calculates every element of the result
+			pub fn realize(self) -> Vector<<Self as VExpr<$n>>::Item, $n> {
+				Vector::build_with_fn(|i| self.at(i))
+			}
+
+			/// calculates every element of a calculation whose elements are calculations
+			/// themselves, like the columns of a [VMatMul]
+			pub fn realize_matrix<const ROWS: usize>(
+				self,
+			) -> Matrix<<<Self as VExpr<$n>>::Item as VExpr<ROWS>>::Item, ROWS, $n>
+			where
+				<Self as VExpr<$n>>::Item: VExpr<ROWS>,
+			{
+				Vector::build_with_fn(|column| {
+					let column = self.at(column);
+					Vector::build_with_fn(|row| column.at(row))
+				})
+			}
+
+			/// adds up all elements of the result without storing them anywhere
+			pub fn sum(self) -> <Self as VExpr<$n>>::Item
+			where
+				<Self as VExpr<$n>>::Item: Sum,
+			{
+				(0..$n).map(|i| self.at(i)).sum()
+			}
+
+			/// the dot product of the result with other, in one pass
+			pub fn dot<Rhs>(self, other: Rhs) -> <<Self as VExpr<$n>>::Item as Mul<Rhs::Item>>::Output
+			where
+				Rhs: VExpr<$n>,
+				<Self as VExpr<$n>>::Item: Mul<Rhs::Item>,
+				<<Self as VExpr<$n>>::Item as Mul<Rhs::Item>>::Output: Sum,
+			{
+				VMul::new(self, other).sum()
+			}
+
+			/// the dot product of the result with itself, calculates every element only once
+			pub fn norm_squared(self) -> <<Self as VExpr<$n>>::Item as Mul>::Output
+			where
+				<Self as VExpr<$n>>::Item: Mul + Copy,
+				<<Self as VExpr<$n>>::Item as Mul>::Output: Sum,
+			{
+				self.map(|e| e * e).sum()
+			}
+
+			/// the smallest element of the result, None if N is 0
+			pub fn min(self) -> Option<<Self as VExpr<$n>>::Item>
+			where
+				<Self as VExpr<$n>>::Item: PartialOrd,
+			{
+				(0..$n).map(|i| self.at(i)).fold(None, |min, e| match min {
+					Some(min) if e < min => Some(e),
+					None => Some(e),
+					min => min,
+				})
+			}
+
+			/// the biggest element of the result, None if N is 0
+			pub fn max(self) -> Option<<Self as VExpr<$n>>::Item>
+			where
+				<Self as VExpr<$n>>::Item: PartialOrd,
+			{
+				(0..$n).map(|i| self.at(i)).fold(None, |max, e| match max {
+					Some(max) if e > max => Some(e),
+					None => Some(e),
+					max => max,
+				})
+			}
+
+			/// swaps rows and columns of a calculation that results in a matrix.
+			/// rows are only calculated once they are accessed.
+			pub fn transpose<const ROWS: usize>(self) -> VTranspose<Self, ROWS, $n>
+			where
+				<Self as VExpr<$n>>::Item: VExpr<ROWS>,
+			{
+				VTranspose::new(self)
+			}
+
+			/// calculates every element of the result, overwriting the contents of target
+			pub fn realize_into(self, target: &mut Vector<<Self as VExpr<$n>>::Item, $n>) {
+				for (i, t) in target.inner.iter_mut().enumerate() {
+					*t = self.at(i);
+				}
+			}
+		}
+	};
+}
+
+macro_rules! node_op {
+	( { $( $generics:tt )* } $name:ty, $n:ident, $op:tt, $fn:ident, $out:ty; $( $bounds:tt )* ) => {
+		impl<$( $generics )*, Rhs> $op<Rhs> for $name
+		where
+			Self: VExpr<$n>,
+			$( $bounds )*
+			Rhs: VExpr<$n>,
+			<Self as VExpr<$n>>::Item: $op<Rhs::Item>,
+		{
+			type Output = $out;
+			fn $fn(self, other: Rhs) -> Self::Output { <$out>::new(self, other) }
+		}
+	};
+}
+
+/// the start of a calculation, turns any leaf into a node so the operators are available.
+///
+/// usually created through .lazy() on Vector, VectorView or TransposedMatrixView
+#[derive(Copy, Clone, Debug)]
+pub struct V<E, const N: usize>(pub E);
+
+impl<E: VExpr<N>, const N: usize> VExpr<N> for V<E, N> {
+	type Item = E::Item;
+	#[inline]
+	fn at(self, index: usize) -> E::Item { self.0.at(index) }
+}
+
+node!({ E, const N: usize } V<E, N>, N);
+
+impl<T, const N: usize> Vector<T, N> {
+	pub fn lazy(&self) -> V<&Self, N> { V(self) }
+}
+
+impl<'a, T, const M: usize, const N: usize> VectorView<'a, T, M, N> {
+	pub fn lazy(self) -> V<Self, M> { V(self) }
+}
+
+impl<'a, T, const M: usize, const N: usize> TransposedMatrixView<'a, T, M, N> {
+	pub fn lazy(self) -> V<Self, N> { V(self) }
+}
+
+/// the sum of two calculations.
+///
+/// T, LT and RT are the element types of the result, l and r. they follow from L and R and are
+/// only there so the type keeps the generics it had before the other nodes were added.
+#[derive(Debug)]
+pub struct VAdd<T, L, R, LT, RT, const N: usize> {
 	l: L,
 	r: R,
-	m: core::marker::PhantomData<(T, LT, RT)>,
+	m: PhantomData<(T, LT, RT)>,
+}
+
+impl<T, L: Copy, R: Copy, LT, RT, const N: usize> Copy for VAdd<T, L, R, LT, RT, N> {}
+impl<T, L: Copy, R: Copy, LT, RT, const N: usize> Clone for VAdd<T, L, R, LT, RT, N> {
+	fn clone(&self) -> Self { *self }
 }
 
-impl<T, L, R, LT, RT, const N: usize> Copy for VAdd<T, L, R, LT, RT, N>
+impl<T, L, R, LT, RT, const N: usize> VAdd<T, L, R, LT, RT, N>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
+	L: VExpr<N, Item = LT>,
+	R: VExpr<N, Item = RT>,
 	LT: Add<RT, Output = T>,
 {
+	pub fn new(l: L, r: R) -> Self { VAdd { l, r, m: PhantomData } }
 }
 
-impl<T, L, R, LT, RT, const N: usize> Clone for VAdd<T, L, R, LT, RT, N>
+impl<T, L, R, LT, RT, const N: usize> VExpr<N> for VAdd<T, L, R, LT, RT, N>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
+	L: VExpr<N, Item = LT>,
+	R: VExpr<N, Item = RT>,
 	LT: Add<RT, Output = T>,
 {
-	fn clone(&self) -> Self { *self }
+	type Item = T;
+	#[inline]
+	fn at(self, index: usize) -> T { self.l.at(index) + self.r.at(index) }
 }
 
-// this is safe because the underlying ConstIndex implementations are guaranteed to be safe
-unsafe impl<T, L, R, LT, RT, const N: usize> ConstIndex<T, N> for VAdd<T, L, R, LT, RT, N>
+node!({ T, L, R, LT, RT, const N: usize } VAdd<T, L, R, LT, RT, N>, N);
+
+binary_node!(VSub, Sub, sub);
+binary_node!(VMul, Mul, mul);
+binary_node!(VDiv, Div, div);
+
+#[derive(Copy, Clone, Debug)]
+pub struct VNeg<E, const N: usize> {
+	e: E,
+}
+
+impl<E: VExpr<N>, const N: usize> VNeg<E, N>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
-	LT: Add<RT, Output = T>,
+	E::Item: Neg,
+{
+	pub fn new(e: E) -> Self { VNeg { e } }
+}
+
+impl<E: VExpr<N>, const N: usize> VExpr<N> for VNeg<E, N>
+where
+	E::Item: Neg,
 {
+	type Item = <E::Item as Neg>::Output;
 	#[inline]
-	fn i(self, index: usize) -> T {
-		let l = self.l.i(index);
-		let r = self.r.i(index);
-		l + r
-	}
+	fn at(self, index: usize) -> Self::Item { -self.e.at(index) }
+}
+
+node!({ E, const N: usize } VNeg<E, N>, N);
+
+/// applies a function to every element
+#[derive(Copy, Clone, Debug)]
+pub struct VMap<E, F, const N: usize> {
+	e: E,
+	f: F,
 }
 
-// this restricts other to const-Index to the same type as self
-//
-// this is not a necessary restriction, but rust type system does not allow for expressing anything
-// more generic due to "unconstrained type parameters"
-//
-// this might be possible once GAT lands, allowing for stuff like (X,Y) + (Z,) = (X, Y, Z) or
-// the like. like for example T + &T which is kinda important...
-impl<T, L, R, LT, RT, O, NT, const N: usize> Add<O> for VAdd<T, L, R, LT, RT, N>
+impl<E: VExpr<N>, F, U, const N: usize> VMap<E, F, N>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
-	LT: Add<RT, Output = T>,
+	F: Fn(E::Item) -> U + Copy,
+{
+	pub fn new(e: E, f: F) -> Self { VMap { e, f } }
+}
 
-	O: ConstIndex<T, N> + Copy + Clone,
-	T: Add<T, Output = NT>,
+impl<E: VExpr<N>, F, U, const N: usize> VExpr<N> for VMap<E, F, N>
+where
+	F: Fn(E::Item) -> U + Copy,
 {
-	type Output = VAdd<NT, Self, O, T, T, N>;
-	fn add(self, other: O) -> Self::Output {
-		VAdd {
-			l: self,
-			r: other,
-			m: Default::default(),
-		}
-	}
+	type Item = U;
+	#[inline]
+	fn at(self, index: usize) -> U { (self.f)(self.e.at(index)) }
+}
+
+node!({ E, F, const N: usize } VMap<E, F, N>, N);
+
+/// swaps rows and columns of a calculation resulting in a (M x N) matrix.
+///
+/// accessing a row takes one element out of every column of the inner calculation. that is cheap
+/// if the columns are lazy, like for leaves or [VMatMul] whose rows cost the same as
+/// [VMatMul::row]. columns that are gathered eagerly, like the rows of another VTranspose, are
+/// calculated in full for every element though.
+#[derive(Copy, Clone, Debug)]
+pub struct VTranspose<E, const M: usize, const N: usize> {
+	e: E,
 }
 
-/*
-// can't even specialize for vector, cause "downstream crates may implement ConstIndex
-// except im already implementing that in this crate...
-use crate::Vector;
-impl<'o, T, L, R, LT, RT, NT, const N: usize> Add<&'o Vector<T, N>> for VAdd<T, L, R, LT, RT, N>
+impl<E: VExpr<N>, const M: usize, const N: usize> VTranspose<E, M, N>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
-	LT: Add<RT, Output = T>,
+	E::Item: VExpr<M>,
+{
+	pub fn new(e: E) -> Self { VTranspose { e } }
+}
 
-	T: Add<&'o T, Output = NT>,
+// a row is gathered into a Vector and wrapped, so it combines with other calculations as a node
+impl<E: VExpr<N>, const M: usize, const N: usize> VExpr<M> for VTranspose<E, M, N>
+where
+	E::Item: VExpr<M>,
+	<E::Item as VExpr<M>>::Item: Copy,
 {
-	type Output = VAdd<NT, Self, &'o Vector<T, N>, T, T, N>;
-	fn add(self, other: &'o Vector<T, N>) -> Self::Output {
-		VAdd {
-			l: self,
-			r: other,
-			m: Default::default(),
-		}
+	type Item = V<Vector<<E::Item as VExpr<M>>::Item, N>, N>;
+	#[inline]
+	fn at(self, row: usize) -> Self::Item {
+		V(Vector::build_with_fn(|column| self.e.at(column).at(row)))
 	}
 }
-*/
-//
-impl<T, L, R, LT, RT, const N: usize> VAdd<T, L, R, LT, RT, N>
+
+node!({ E, const M: usize, const N: usize } VTranspose<E, M, N>, M);
+
+/// the product of a (M x N) and a (N x O) matrix.
+///
+/// the columns are [VMatVec] nodes, so they only get calculated as far as they are accessed
+#[derive(Debug)]
+pub struct VMatMul<'a, 'b, T, const M: usize, const N: usize, const O: usize> {
+	a: &'a Matrix<T, M, N>,
+	b: &'b Matrix<T, N, O>,
+}
+
+impl<'a, 'b, T, const M: usize, const N: usize, const O: usize> Copy
+	for VMatMul<'a, 'b, T, M, N, O>
+{
+}
+impl<'a, 'b, T, const M: usize, const N: usize, const O: usize> Clone
+	for VMatMul<'a, 'b, T, M, N, O>
+{
+	fn clone(&self) -> Self { *self }
+}
+
+impl<'a, 'b, T: 'a + 'b, const M: usize, const N: usize, const O: usize> VMatMul<'a, 'b, T, M, N, O>
 where
-	L: ConstIndex<LT, N> + Copy + Clone,
-	R: ConstIndex<RT, N> + Copy + Clone,
-	LT: Add<RT, Output = T>,
+	T: Copy + Mul<Output = T> + Sum,
 {
-	pub fn new(l: L, r: R) -> Self {
-		Self {
-			l,
-			r,
-			m: Default::default(),
-		}
+	pub fn new(a: &'a Matrix<T, M, N>, b: &'b Matrix<T, N, O>) -> Self { VMatMul { a, b } }
+
+	/// a single element of the product
+	pub fn element(self, row: usize, column: usize) -> T {
+		(0..N).map(|k| self.a[k][row] * self.b[column][k]).sum()
+	}
+
+	/// a single row of the product, without calculating any other element
+	pub fn row(self, row: usize) -> Vector<T, O> {
+		Vector::build_with_fn(|column| self.element(row, column))
 	}
+}
+
+impl<'a, 'b, T: 'a + 'b, const M: usize, const N: usize, const O: usize> VExpr<O>
+	for VMatMul<'a, 'b, T, M, N, O>
+where
+	T: Copy + Mul<Output = T> + Sum,
+{
+	type Item = VMatVec<'a, 'b, T, M, N>;
+	#[inline]
+	fn at(self, column: usize) -> VMatVec<'a, 'b, T, M, N> { VMatVec::new(self.a, &self.b[column]) }
+}
+
+node!({ 'a, 'b, T: 'a + 'b, const M: usize, const N: usize, const O: usize }
+	VMatMul<'a, 'b, T, M, N, O>, O where T: Copy + Mul<Output = T> + Sum,);
 
-	pub fn realize(self) -> crate::Vector<T, N> { ConstIterator::from(self).collect() }
+/// the product of a (M x N) matrix and a N element vector
+#[derive(Debug)]
+pub struct VMatVec<'a, 'b, T, const M: usize, const N: usize> {
+	a: &'a Matrix<T, M, N>,
+	x: &'b Vector<T, N>,
+}
+
+impl<'a, 'b, T, const M: usize, const N: usize> Copy for VMatVec<'a, 'b, T, M, N> {}
+impl<'a, 'b, T, const M: usize, const N: usize> Clone for VMatVec<'a, 'b, T, M, N> {
+	fn clone(&self) -> Self { *self }
+}
+
+impl<'a, 'b, T: 'a + 'b, const M: usize, const N: usize> VMatVec<'a, 'b, T, M, N>
+where
+	T: Copy + Mul<Output = T> + Sum,
+{
+	pub fn new(a: &'a Matrix<T, M, N>, x: &'b Vector<T, N>) -> Self { VMatVec { a, x } }
+}
+
+impl<'a, 'b, T: 'a + 'b, const M: usize, const N: usize> VExpr<M> for VMatVec<'a, 'b, T, M, N>
+where
+	T: Copy + Mul<Output = T> + Sum,
+{
+	type Item = T;
+	#[inline]
+	fn at(self, row: usize) -> T { (0..N).map(|k| self.a[k][row] * self.x[k]).sum() }
+}
+
+node!({ 'a, 'b, T: 'a + 'b, const M: usize, const N: usize }
+	VMatVec<'a, 'b, T, M, N>, M where T: Copy + Mul<Output = T> + Sum,);
+
+impl<'a, 'b, T: 'a + 'b, const M: usize, const N: usize> Matrix<T, M, N>
+where
+	T: Copy + Mul<Output = T> + Sum,
+{
+	/// like [matrix_multiply](#method.matrix_multiply) but nothing is calculated until needed
+	pub fn lazy_matrix_multiply<const O: usize>(
+		&'a self,
+		other: &'b Matrix<T, N, O>,
+	) -> VMatMul<'a, 'b, T, M, N, O> {
+		VMatMul { a: self, b: other }
+	}
+
+	/// self * x, calculated element by element
+	pub fn lazy_multiply_vector(&'a self, x: &'b Vector<T, N>) -> VMatVec<'a, 'b, T, M, N> {
+		VMatVec { a: self, x }
+	}
 }
 
 #[cfg(test)]
@@ -143,10 +541,110 @@ fn calc_chain() {
 	let d: Vector<f32, TESTLEN> = rng.gen();
 	let e: Vector<f32, TESTLEN> = rng.gen();
 
-	let ab = VAdd::new(a, b);
+	// the generics VAdd had before the other nodes existed
+	type Leaf = Vector<f32, TESTLEN>;
+	let ab: VAdd<f32, Leaf, Leaf, f32, f32, TESTLEN> = VAdd::new(a, b);
 	let abc = ab + c;
 	let abcd = abc + d;
 	let abcde = abcd + e;
 
 	let _res = abcde.realize();
 }
+
+#[test]
+// borrowing the leaves is deliberate, using them by value would copy the whole Vector per element
+#[allow(clippy::op_ref)]
+fn mixed_nodes() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	let a: Vector<f32, TESTLEN> = rng.gen();
+	let b: Vector<f32, TESTLEN> = rng.gen();
+	let c: Vector<f32, TESTLEN> = rng.gen();
+	let d: Vector<f32, TESTLEN> = rng.gen();
+
+	let lazy = (VAdd::new(&a, &b) * &c - VMap::new(&d, |x: &f32| x * 2.)).realize();
+	let eager = &(&(&a + &b) * &c) - &(&d * &Vector::splat(2.));
+	assert_eq!(lazy, eager);
+
+	let lazy = (-VDiv::new(&a, 4f32) + 1f32).map(|x| x * x).realize();
+	for i in 0..TESTLEN {
+		assert_eq!(lazy[i], (1. - a[i] / 4.) * (1. - a[i] / 4.));
+	}
+
+	// views as leaves
+	let m: crate::Matrix<f32, 3, 2> = rng.gen();
+	let row = m.transpose().i(1);
+	let lazy: Vector<f32, 2> = VSub::new(row, row).realize();
+	assert_eq!(lazy, Vector::zeros());
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn lazy_start() {
+	use crate::Matrix;
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	let a: Vector<f32, TESTLEN> = rng.gen();
+	let b: Vector<f32, TESTLEN> = rng.gen();
+	let c: Vector<f32, TESTLEN> = rng.gen();
+
+	type Leaf<'a> = V<&'a Vector<f32, TESTLEN>, TESTLEN>;
+	let chain: VAdd<_, VAdd<_, Leaf, _, _, _, TESTLEN>, _, _, _, TESTLEN> = a.lazy() + &b + &c;
+	assert_eq!(chain.realize(), &(&a + &b) + &c);
+
+	let mut target = Vector::default();
+	(V(&a) * 2f32).realize_into(&mut target);
+	assert_eq!(target, &a + &a);
+
+	// row-wise operations on matrices
+	let m: Matrix<f32, 3, 4> = rng.gen();
+	let n: Matrix<f32, 4, 3> = rng.gen();
+	let sum: Vector<Vector<f32, 4>, 3> = (m.transpose().lazy() + &n).realize();
+	assert_eq!(sum, &m.transpose().materialize() + &n);
+	let row = m.transpose().i(2);
+	assert_eq!((row.lazy() - row).realize(), Vector::<f32, 4>::zeros());
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn reductions() {
+	let a = Vector::from([1., -2., 3.]);
+	let b = Vector::from([4., 5., -6.]);
+
+	assert_eq!((a.lazy() + &b).sum(), 5.);
+	assert_eq!((a.lazy() - &b).norm_squared(), 9. + 49. + 81.);
+	assert_eq!((a.lazy() * 2.).dot(&b), 2. * a.dot(&b));
+	assert_eq!((a.lazy() + &b).min(), Some(-3.));
+	assert_eq!((a.lazy() + &b).max(), Some(5.));
+	assert_eq!(a.lazy().max(), Some(&3.));
+	assert_eq!(Vector::<f32, 0>::default().lazy().max(), None);
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn lazy_matrices() {
+	use rand::{thread_rng, Rng};
+	let mut rng = thread_rng();
+	let a: Matrix<i32, 3, 4> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let b: Matrix<i32, 4, 2> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let c: Matrix<i32, 3, 2> = Matrix::from_fn(|_, _| rng.gen_range(-100, 100));
+	let x: Vector<i32, 4> = Vector::build_with_fn(|_| rng.gen_range(-100, 100));
+	let y: Vector<i32, 3> = Vector::build_with_fn(|_| rng.gen_range(-100, 100));
+
+	let ab = a.matrix_multiply(&b);
+	assert_eq!(a.lazy_matrix_multiply(&b).realize_matrix(), ab);
+	assert_eq!((a.lazy_matrix_multiply(&b) + &c).realize_matrix(), &ab + &c);
+	assert_eq!(a.lazy_matrix_multiply(&b).at(1).at(2), ab[1][2]);
+	assert_eq!(a.lazy_matrix_multiply(&b).row(1), ab.transpose().materialize()[1]);
+	assert_eq!(
+		(a.lazy_matrix_multiply(&b) - &c).transpose().realize_matrix(),
+		(&ab - &c).transpose().materialize()
+	);
+	// leaves borrow, so the transpose of a plain matrix holds references
+	let ct = c.lazy().transpose().realize_matrix();
+	assert_eq!(*ct[2][1], c[1][2]);
+
+	let ax = a.matrix_multiply(&x.ascend())[0];
+	let residual = (a.lazy_multiply_vector(&x) - &y).realize();
+	assert_eq!(residual, &ax - &y);
+}
@@ -0,0 +1,422 @@
+//! human readable exchange formats: Matrix Market and delimited text like csv.
+//!
+//! parsing works on a `&str` and writing on any `fmt::Write`, so none of this needs an allocator.
+//! ```
+//! use optimath::{text::Delimited, Matrix};
+//! let m: Matrix<f64, 2, 3> = Matrix::from_fn(|row, column| (row * 3 + column) as f64 / 4.);
+//! let mut csv = String::new();
+//! m.write_delimited(&mut csv, &Delimited::default()).unwrap();
+//! assert_eq!(csv, "0,0.25,0.5\n0.75,1,1.25\n");
+//! assert_eq!(Matrix::from_delimited(&csv, &Delimited::default()), Ok(m));
+//!
+//! let mut market = String::new();
+//! m.write_matrix_market_array(&mut market).unwrap();
+//! assert!(market.starts_with("%%MatrixMarket matrix array real general\n2 3\n"));
+//! assert_eq!(Matrix::from_matrix_market(&market), Ok(m));
+//! ```
+use crate::{
+	num::Zero,
+	types::{Matrix, Vector},
+};
+use core::{
+	fmt::{self, Display, Write},
+	str::FromStr,
+};
+
+/// why a text could not be read into a Matrix.
+///
+/// lines are counted from 1 like in an editor, columns from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextError {
+	/// the header or size line is missing, malformed or describes something unsupported
+	Header { line: usize },
+	/// the number of rows does not match M
+	Rows { expected: usize, found: usize },
+	/// a row has the wrong number of cells, or the size line the wrong number of columns
+	Columns { line: usize, expected: usize, found: usize },
+	/// the number of entries in a Matrix Market file does not match its size line
+	Entries { expected: usize, found: usize },
+	/// a coordinate outside of the matrix, or above the diagonal in a symmetric file
+	Index { line: usize },
+	/// a coordinate that already had an entry
+	Duplicate { line: usize },
+	/// a cell that T::from_str did not accept
+	Cell { line: usize, column: usize },
+}
+
+impl Display for TextError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TextError::Header { line } => write!(f, "line {}: invalid or unsupported header", line),
+			TextError::Rows { expected, found } => {
+				write!(f, "expected {} rows but found {}", expected, found)
+			},
+			TextError::Columns { line, expected, found } => {
+				write!(f, "line {}: expected {} columns but found {}", line, expected, found)
+			},
+			TextError::Entries { expected, found } => {
+				write!(f, "expected {} entries but found {}", expected, found)
+			},
+			TextError::Index { line } => write!(f, "line {}: index out of bounds", line),
+			TextError::Duplicate { line } => write!(f, "line {}: duplicate entry", line),
+			TextError::Cell { line, column } => {
+				write!(f, "line {}: can not parse column {}", line, column)
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TextError {}
+
+/// the layout of delimited text, one line per row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delimited<'a> {
+	/// between the cells of a row, surrounding whitespace is ignored when reading
+	pub separator: char,
+	/// the column names, written as the first line. reading skips the first line if this is set
+	pub header: Option<&'a [&'a str]>,
+	/// digits after the decimal point when writing, None writes every digit Display does
+	pub precision: Option<usize>,
+}
+
+/// comma separated, no header and full precision
+impl Default for Delimited<'_> {
+	fn default() -> Self { Delimited { separator: ',', header: None, precision: None } }
+}
+
+fn write_cell<W: Write, T: Display>(out: &mut W, x: &T, precision: Option<usize>) -> fmt::Result {
+	match precision {
+		Some(precision) => write!(out, "{:.*}", precision, x),
+		None => write!(out, "{}", x),
+	}
+}
+
+fn parse_cell<T: FromStr>(cell: &str, line: usize, column: usize) -> Result<T, TextError> {
+	cell.trim().parse().map_err(|_| TextError::Cell { line, column })
+}
+
+/// the non-empty lines that are not comments, with their line numbers
+fn content_lines(text: &str, comment: Option<char>) -> impl Iterator<Item = (usize, &str)> {
+	text.lines()
+		.enumerate()
+		.map(|(i, line)| (i + 1, line.trim()))
+		.filter(move |(_, line)| !line.is_empty() && !comment.is_some_and(|c| line.starts_with(c)))
+}
+
+/// the element type written into Matrix Market headers
+pub trait MarketField {
+	const FIELD: &'static str;
+}
+
+macro_rules! market_field {
+	( $field:literal; $( $t:ty ), * ) => {
+		$(
+			impl MarketField for $t {
+				const FIELD: &'static str = $field;
+			}
+		)*
+	};
+}
+
+market_field!("integer"; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+market_field!("real"; f32, f64);
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
+	/// parses one row per line, see [Delimited] for the options
+	pub fn from_delimited(text: &str, format: &Delimited) -> Result<Self, TextError>
+	where
+		T: FromStr + Zero + Copy,
+	{
+		let mut lines = content_lines(text, None).skip(format.header.is_some() as usize);
+		let mut matrix = Matrix::<T, M, N>::zero();
+		let mut rows = 0;
+		for (line, text) in lines.by_ref().take(M) {
+			let mut found = 0;
+			for (column, cell) in text.split(format.separator).enumerate() {
+				if column < N {
+					matrix[column][rows] = parse_cell(cell, line, column)?;
+				}
+				found += 1;
+			}
+			if found != N {
+				return Err(TextError::Columns { line, expected: N, found });
+			}
+			rows += 1;
+		}
+		let found = rows + lines.count();
+		if found != M {
+			return Err(TextError::Rows { expected: M, found });
+		}
+		Ok(matrix)
+	}
+
+	/// writes one row per line, every line ends in a newline
+	pub fn write_delimited<W: Write>(&self, out: &mut W, format: &Delimited) -> fmt::Result
+	where
+		T: Display,
+	{
+		if let Some(names) = format.header {
+			for (i, name) in names.iter().enumerate() {
+				if i > 0 {
+					out.write_char(format.separator)?;
+				}
+				out.write_str(name)?;
+			}
+			out.write_char('\n')?;
+		}
+		for row in 0..M {
+			for column in 0..N {
+				if column > 0 {
+					out.write_char(format.separator)?;
+				}
+				write_cell(out, &self[column][row], format.precision)?;
+			}
+			out.write_char('\n')?;
+		}
+		Ok(())
+	}
+
+	/// parses the array and the coordinate format, general or symmetric, real or integer.
+	///
+	/// entries missing from a coordinate file are zero.
+	pub fn from_matrix_market(text: &str) -> Result<Self, TextError>
+	where
+		T: FromStr + Zero + Copy,
+	{
+		let header = TextError::Header { line: 1 };
+		let mut words = text.lines().next().unwrap_or("").split_whitespace();
+		// the index of the word in options, the banner is case insensitive
+		let mut word = |options: &[&str]| {
+			let word = words.next().ok_or(header)?;
+			options.iter().position(|o| o.eq_ignore_ascii_case(word)).ok_or(header)
+		};
+		word(&["%%MatrixMarket"])?;
+		word(&["matrix"])?;
+		let coordinate = word(&["array", "coordinate"])? == 1;
+		word(&["real", "integer"])?;
+		let symmetric = word(&["general", "symmetric"])? == 1;
+		if words.next().is_some() {
+			return Err(header);
+		}
+
+		let mut lines = content_lines(text, Some('%'));
+		// without a size line, the last line there is was the last one read
+		let last = TextError::Header { line: text.lines().count().max(1) };
+		let (line, size) = lines.next().ok_or(last)?;
+		let mut size = size.split_whitespace().enumerate().map(|(i, s)| parse_cell(s, line, i));
+		let (rows, columns) = (size.next().transpose()?, size.next().transpose()?);
+		let entries = if coordinate { size.next().transpose()? } else { None };
+		let (rows, columns) = match (rows, columns, size.next()) {
+			(Some(rows), Some(columns), None) if !coordinate || entries.is_some() => {
+				(rows, columns)
+			},
+			_ => return Err(TextError::Header { line }),
+		};
+		if rows != M {
+			return Err(TextError::Rows { expected: M, found: rows });
+		}
+		if columns != N {
+			return Err(TextError::Columns { line, expected: N, found: columns });
+		}
+		if symmetric && M != N {
+			return Err(TextError::Header { line });
+		}
+
+		let mut matrix = Matrix::<T, M, N>::zero();
+		let mut found = 0;
+		if coordinate {
+			let mut seen = Matrix::<bool, M, N>::from_fn(|_, _| false);
+			for (line, text) in lines {
+				let mut cells = text.split_whitespace();
+				let cells = [cells.next(), cells.next(), cells.next(), cells.next()];
+				let (row, column, value) = match cells {
+					[Some(row), Some(column), Some(value), None] => (row, column, value),
+					_ => {
+						let found = cells.iter().flatten().count();
+						return Err(TextError::Columns { line, expected: 3, found });
+					},
+				};
+				// one based
+				let row = parse_cell::<usize>(row, line, 0)?.wrapping_sub(1);
+				let column = parse_cell::<usize>(column, line, 1)?.wrapping_sub(1);
+				// symmetric files only contain the lower triangle
+				if row >= M || column >= N || (symmetric && row < column) {
+					return Err(TextError::Index { line });
+				}
+				if seen[column][row] {
+					return Err(TextError::Duplicate { line });
+				}
+				seen[column][row] = true;
+				matrix[column][row] = parse_cell(value, line, 2)?;
+				if symmetric {
+					matrix[row][column] = matrix[column][row];
+				}
+				found += 1;
+			}
+		} else {
+			// column major, symmetric files only contain the lower triangle
+			let mut positions = (0..N)
+				.flat_map(|column| (0..M).map(move |row| (row, column)))
+				.filter(|(row, column)| !symmetric || row >= column);
+			for (line, text) in lines {
+				let (row, column) = match positions.next() {
+					Some(position) => position,
+					None => {
+						found += 1;
+						continue;
+					},
+				};
+				matrix[column][row] = parse_cell(text, line, 0)?;
+				if symmetric {
+					matrix[row][column] = matrix[column][row];
+				}
+				found += 1;
+			}
+		}
+		let expected = match (coordinate, symmetric) {
+			(true, _) => entries.unwrap_or(0),
+			(false, true) => N * (N + 1) / 2,
+			(false, false) => M * N,
+		};
+		if found != expected {
+			return Err(TextError::Entries { expected, found });
+		}
+		Ok(matrix)
+	}
+
+	/// writes every element in the dense array format
+	pub fn write_matrix_market_array<W: Write>(&self, out: &mut W) -> fmt::Result
+	where
+		T: Display + MarketField,
+	{
+		writeln!(out, "%%MatrixMarket matrix array {} general", T::FIELD)?;
+		writeln!(out, "{} {}", M, N)?;
+		for value in self.into_iter().flat_map(Vector::as_slice) {
+			writeln!(out, "{}", value)?;
+		}
+		Ok(())
+	}
+
+	/// writes the non-zero elements in the sparse coordinate format
+	pub fn write_matrix_market_coordinate<W: Write>(&self, out: &mut W) -> fmt::Result
+	where
+		T: Display + MarketField + Zero + PartialEq,
+	{
+		let zero = T::zero();
+		let non_zero = self.into_iter().flat_map(Vector::as_slice).filter(|v| **v != zero);
+		writeln!(out, "%%MatrixMarket matrix coordinate {} general", T::FIELD)?;
+		writeln!(out, "{} {} {}", M, N, non_zero.count())?;
+		for (column, values) in self.into_iter().enumerate() {
+			for (row, value) in values.into_iter().enumerate().filter(|(_, v)| **v != zero) {
+				writeln!(out, "{} {} {}", row + 1, column + 1, value)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+extern crate std;
+#[cfg(test)]
+use std::string::String;
+
+#[test]
+fn delimited() {
+	let names = ["a", "b"];
+	let format = Delimited { separator: ';', header: Some(&names), precision: Some(2) };
+	let m: Matrix<f32, 3, 2> = Matrix::from_fn(|row, column| row as f32 - column as f32 / 3.);
+	let mut text = String::new();
+	m.write_delimited(&mut text, &format).unwrap();
+	assert_eq!(text, "a;b\n0.00;-0.33\n1.00;0.67\n2.00;1.67\n");
+	let parsed: Matrix<f32, 3, 2> = Matrix::from_delimited(&text, &format).unwrap();
+	assert_eq!(parsed[1][2], 1.67);
+
+	let csv = "1, 2, 3\n\n4, 5, 6\n";
+	let m: Matrix<i32, 2, 3> = Matrix::from_delimited(csv, &Delimited::default()).unwrap();
+	assert_eq!(m[2], Vector::from_array([3, 6]));
+
+	let read = |text| Matrix::<i32, 2, 3>::from_delimited(text, &Delimited::default());
+	assert_eq!(read("1,2,3\n4,5\n"), Err(TextError::Columns { line: 2, expected: 3, found: 2 }));
+	assert_eq!(read("1,2,3\n"), Err(TextError::Rows { expected: 2, found: 1 }));
+	assert_eq!(read("1,2,3\n4,5,6\n7,8,9"), Err(TextError::Rows { expected: 2, found: 3 }));
+	assert_eq!(read("1,2,3\n4,x,6\n"), Err(TextError::Cell { line: 2, column: 1 }));
+}
+
+#[test]
+fn matrix_market() {
+	let coordinate = "%%MatrixMarket matrix coordinate integer symmetric
+% a comment
+3 3 3
+1 1 5
+3 1 -2
+2 2 7
+";
+	let m: Matrix<i64, 3, 3> = Matrix::from_matrix_market(coordinate).unwrap();
+	assert_eq!(m[0], Vector::from_array([5, 0, -2]));
+	assert_eq!(m[2], Vector::from_array([-2, 0, 0]));
+
+	let mut text = String::new();
+	m.write_matrix_market_coordinate(&mut text).unwrap();
+	assert!(text.starts_with("%%MatrixMarket matrix coordinate integer general\n3 3 4\n1 1 5\n"));
+	assert_eq!(Matrix::from_matrix_market(&text), Ok(m));
+
+	let array = "%%MatrixMarket matrix array real symmetric\n2 2\n1.5\n2\n3\n";
+	let m: Matrix<f32, 2, 2> = Matrix::from_matrix_market(array).unwrap();
+	assert_eq!((m[0][0], m[0][1], m[1][0], m[1][1]), (1.5, 2., 2., 3.));
+
+	let read = Matrix::<f32, 2, 2>::from_matrix_market;
+	let complex = "%%MatrixMarket matrix array complex general\n2 2\n";
+	assert_eq!(read(complex), Err(TextError::Header { line: 1 }));
+	assert_eq!(
+		read("%%MatrixMarket matrix array real general\n3 2\n"),
+		Err(TextError::Rows { expected: 2, found: 3 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix array real general\n2 2\n1\n2\n3\n"),
+		Err(TextError::Entries { expected: 4, found: 3 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix coordinate real general\n2 2 1\n3 1 1.0\n"),
+		Err(TextError::Index { line: 3 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 1\n"),
+		Err(TextError::Columns { line: 3, expected: 3, found: 2 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 1.0\n1 2 3.0\n"),
+		Err(TextError::Duplicate { line: 4 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix coordinate real symmetric\n2 2 1\n1 2 1.0\n"),
+		Err(TextError::Index { line: 3 })
+	);
+	assert_eq!(
+		read("%%MatrixMarket matrix array real general\n% no size line\n\n"),
+		Err(TextError::Header { line: 3 })
+	);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn round_trip() {
+	use rand::{rngs::StdRng, Rng, SeedableRng};
+	let mut rng = StdRng::seed_from_u64(48);
+	let tsv = Delimited { separator: '\t', ..Delimited::default() };
+	for _ in 0..20 {
+		let m: Matrix<f64, 4, 7> = rng.gen();
+		let mut text = String::new();
+		m.write_delimited(&mut text, &tsv).unwrap();
+		assert_eq!(Matrix::from_delimited(&text, &tsv), Ok(m));
+
+		let mut text = String::new();
+		m.write_matrix_market_array(&mut text).unwrap();
+		assert_eq!(Matrix::from_matrix_market(&text), Ok(m));
+
+		let sparse: Matrix<i32, 5, 3> = Matrix::from_fn(|_, _| rng.gen_range(-1, 2));
+		let mut text = String::new();
+		sparse.write_matrix_market_coordinate(&mut text).unwrap();
+		assert_eq!(Matrix::from_matrix_market(&text), Ok(sparse));
+	}
+}
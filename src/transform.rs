@@ -0,0 +1,287 @@
+//! 4x4 homogeneous transforms and rigid body motions.
+//!
+//! matrices follow the opengl conventions: right handed, the camera looks down -z and clip space
+//! depth goes from -1 to 1. vectors are columns, so transforms apply right to left.
+use crate::{
+	num::{Float, Zero},
+	quaternion::Quaternion,
+	types::{Matrix, Vector},
+};
+use core::ops::Mul;
+
+impl<T: Float> Matrix<T, 4, 4> {
+	fn from_row_arrays(rows: [[T; 4]; 4]) -> Self {
+		Matrix::from_fn(|row, column| rows[row][column])
+	}
+
+	pub fn translation(offset: &Vector<T, 3>) -> Self {
+		let (o, z) = (T::one(), T::zero());
+		Self::from_row_arrays([
+			[o, z, z, offset[0]],
+			[z, o, z, offset[1]],
+			[z, z, o, offset[2]],
+			[z, z, z, o],
+		])
+	}
+
+	pub fn scaling(factors: &Vector<T, 3>) -> Self {
+		let (o, z) = (T::one(), T::zero());
+		Self::from_row_arrays([
+			[factors[0], z, z, z],
+			[z, factors[1], z, z],
+			[z, z, factors[2], z],
+			[z, z, z, o],
+		])
+	}
+
+	/// counterclockwise around the x axis, in radians
+	pub fn rotation_x(angle: T) -> Self {
+		let (o, z, s, c) = (T::one(), T::zero(), angle.sin(), angle.cos());
+		Self::from_row_arrays([[o, z, z, z], [z, c, -s, z], [z, s, c, z], [z, z, z, o]])
+	}
+
+	/// counterclockwise around the y axis, in radians
+	pub fn rotation_y(angle: T) -> Self {
+		let (o, z, s, c) = (T::one(), T::zero(), angle.sin(), angle.cos());
+		Self::from_row_arrays([[c, z, s, z], [z, o, z, z], [-s, z, c, z], [z, z, z, o]])
+	}
+
+	/// counterclockwise around the z axis, in radians
+	pub fn rotation_z(angle: T) -> Self {
+		let (o, z, s, c) = (T::one(), T::zero(), angle.sin(), angle.cos());
+		Self::from_row_arrays([[c, -s, z, z], [s, c, z, z], [z, z, o, z], [z, z, z, o]])
+	}
+
+	/// the rotation of a unit quaternion
+	pub fn from_quaternion(q: &Quaternion<T>) -> Self {
+		Isometry::from_quaternion(q, &Vector::zero()).to_matrix()
+	}
+
+	/// a view matrix for a camera at eye looking at target
+	pub fn look_at(eye: &Vector<T, 3>, target: &Vector<T, 3>, up: &Vector<T, 3>) -> Self {
+		let forward =
+			Vector::from_array([target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]])
+				.normalize();
+		let side = forward.cross(up).normalize();
+		let up = side.cross(&forward);
+		let (o, z) = (T::one(), T::zero());
+		Self::from_row_arrays([
+			[side[0], side[1], side[2], -side.fdot(eye)],
+			[up[0], up[1], up[2], -up.fdot(eye)],
+			[-forward[0], -forward[1], -forward[2], forward.fdot(eye)],
+			[z, z, z, o],
+		])
+	}
+
+	/// fov_y is the vertical field of view in radians, aspect is width / height
+	pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Self {
+		let (o, z) = (T::one(), T::zero());
+		let two = o + o;
+		let half = fov_y / two;
+		let f = half.cos() / half.sin();
+		let depth = near - far;
+		Self::from_row_arrays([
+			[f / aspect, z, z, z],
+			[z, f, z, z],
+			[z, z, (far + near) / depth, two * far * near / depth],
+			[z, z, -o, z],
+		])
+	}
+
+	pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+		let (o, z) = (T::one(), T::zero());
+		let two = o + o;
+		let (width, height, depth) = (right - left, top - bottom, far - near);
+		Self::from_row_arrays([
+			[two / width, z, z, -(right + left) / width],
+			[z, two / height, z, -(top + bottom) / height],
+			[z, z, -two / depth, -(far + near) / depth],
+			[z, z, z, o],
+		])
+	}
+
+	/// applies self to the point p (w = 1), including the divide by the resulting w
+	pub fn transform_point(&self, p: &Vector<T, 3>) -> Vector<T, 3> {
+		let mut out = self[3];
+		for (column, p) in self.inner[..3].iter().zip(p) {
+			out.axpy(*p, column);
+		}
+		let w = out[3];
+		Vector::from_array([out[0] / w, out[1] / w, out[2] / w])
+	}
+
+	/// applies self to the direction v (w = 0), so translations and the divide are skipped
+	pub fn transform_vector(&self, v: &Vector<T, 3>) -> Vector<T, 3> {
+		let mut out = Vector::zero();
+		for (column, v) in self.inner[..3].iter().zip(v) {
+			out.axpy(*v, column);
+		}
+		out.truncate()
+	}
+}
+
+/// a rotation followed by a translation, i.e. a rigid body motion.
+///
+/// cheaper than a full 4x4 matrix, especially to invert.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Isometry<T> {
+	pub(crate) rotation: Matrix<T, 3, 3>,
+	pub(crate) translation: Vector<T, 3>,
+}
+
+impl<T: Float> Isometry<T> {
+	/// rotation has to be orthogonal with a determinant of 1
+	pub fn new(rotation: Matrix<T, 3, 3>, translation: Vector<T, 3>) -> Self {
+		Isometry {
+			rotation,
+			translation,
+		}
+	}
+
+	pub fn identity() -> Self { Isometry::new(Matrix::identity(), Vector::zero()) }
+
+	pub fn from_quaternion(rotation: &Quaternion<T>, translation: &Vector<T, 3>) -> Self {
+		Isometry::new(rotation.to_rotation_matrix(), *translation)
+	}
+
+	pub fn rotation(&self) -> &Matrix<T, 3, 3> { &self.rotation }
+	pub fn translation(&self) -> &Vector<T, 3> { &self.translation }
+
+	/// transposes the rotation and rotates the negated translation back
+	pub fn inverse(&self) -> Self {
+		let rotation = self.rotation.transpose().materialize();
+		let mut translation = Vector::zero();
+		translation.gemv(-T::one(), &rotation, &self.translation, T::zero());
+		Isometry {
+			rotation,
+			translation,
+		}
+	}
+
+	pub fn transform_point(&self, p: &Vector<T, 3>) -> Vector<T, 3> {
+		let mut out = self.translation;
+		out.gemv(T::one(), &self.rotation, p, T::one());
+		out
+	}
+
+	pub fn transform_vector(&self, v: &Vector<T, 3>) -> Vector<T, 3> {
+		let mut out = Vector::zero();
+		out.gemv(T::one(), &self.rotation, v, T::zero());
+		out
+	}
+
+	/// the same transform as a homogeneous matrix
+	pub fn to_matrix(&self) -> Matrix<T, 4, 4> {
+		let mut columns = self.rotation.into_iter().map(|c| c.extend(T::zero()));
+		let (a, b, c) = (
+			columns.next().unwrap(),
+			columns.next().unwrap(),
+			columns.next().unwrap(),
+		);
+		Matrix::from_columns([a, b, c, self.translation.extend(T::one())])
+	}
+}
+
+/// self * other applies other first
+impl<T: Float> Mul for Isometry<T> {
+	type Output = Isometry<T>;
+	fn mul(self, other: Self) -> Self {
+		let mut rotation = Matrix::zero();
+		rotation.gemm(T::one(), &self.rotation, &other.rotation, T::zero());
+		Isometry {
+			rotation,
+			translation: self.transform_point(&other.translation),
+		}
+	}
+}
+
+#[test]
+fn transforms() {
+	use core::f64::consts::FRAC_PI_2;
+	let p = Vector::from_array([1., 2., 3.]);
+	let x = Vector::from_array([1., 0., 0.]);
+	let y = Vector::from_array([0., 1., 0.]);
+	let z = Vector::from_array([0., 0., 1.]);
+
+	let t = Matrix::translation(&Vector::from_array([1., 1., 1.]));
+	assert_eq!(t.transform_point(&p), Vector::from_array([2., 3., 4.]));
+	assert_eq!(t.transform_vector(&p), p);
+	let s = Matrix::scaling(&Vector::from_array([2., 3., 4.]));
+	assert_eq!(s.transform_point(&p), Vector::from_array([2., 6., 12.]));
+
+	crate::assert_approx_eq!(
+		Matrix::rotation_x(FRAC_PI_2).transform_vector(&y),
+		z,
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		Matrix::rotation_y(FRAC_PI_2).transform_vector(&z),
+		x,
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		Matrix::rotation_z(FRAC_PI_2).transform_vector(&x),
+		y,
+		epsilon = 1e-12,
+	);
+	let q = Quaternion::from_axis_angle(&x, FRAC_PI_2);
+	crate::assert_approx_eq!(
+		Matrix::from_quaternion(&q).transform_point(&p),
+		q.rotate(&p),
+		epsilon = 1e-12,
+	);
+
+	// the camera sits on +z looking at the origin, so the origin ends up straight ahead
+	let view = Matrix::look_at(&Vector::from_array([0., 0., 5.]), &Vector::zero(), &y);
+	crate::assert_approx_eq!(
+		view.transform_point(&Vector::zero()),
+		Vector::from_array([0., 0., -5.]),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		view.transform_point(&x),
+		Vector::from_array([1., 0., -5.]),
+		epsilon = 1e-12,
+	);
+
+	let perspective = Matrix::perspective(FRAC_PI_2, 2., 1., 10.);
+	crate::assert_approx_eq!(
+		perspective.transform_point(&Vector::from_array([0., 0., -1.])),
+		Vector::from_array([0., 0., -1.]),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		perspective.transform_point(&Vector::from_array([2., 1., -10.])),
+		Vector::from_array([0.1, 0.1, 1.]),
+		epsilon = 1e-12,
+	);
+	let ortho = Matrix::orthographic(-2., 2., -1., 1., 1., 10.);
+	crate::assert_approx_eq!(
+		ortho.transform_point(&Vector::from_array([2., -1., -10.])),
+		Vector::from_array([1., -1., 1.]),
+		epsilon = 1e-12,
+	);
+
+	let a = Isometry::from_quaternion(&q, &Vector::from_array([1., 0., 0.]));
+	let b = Isometry::from_quaternion(
+		&Quaternion::from_axis_angle(&z, 0.4),
+		&Vector::from_array([0., -2., 1.]),
+	);
+	crate::assert_approx_eq!(
+		a.inverse().transform_point(&a.transform_point(&p)),
+		p,
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		(a * b).transform_point(&p),
+		a.transform_point(&b.transform_point(&p)),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(
+		a.to_matrix().transform_point(&p),
+		a.transform_point(&p),
+		epsilon = 1e-12,
+	);
+	crate::assert_approx_eq!(a.transform_vector(&y), z, epsilon = 1e-12);
+	assert_eq!(Isometry::identity().transform_point(&p), p);
+}
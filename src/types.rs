@@ -2,9 +2,11 @@
 //! intentionally unusable above the size of 32. When that restriction gets removed most of this
 //! module gets obsolete
 use core::{
+	borrow::{Borrow, BorrowMut},
 	iter::{FromIterator, IntoIterator},
 	mem::MaybeUninit,
 	ops::*,
+	slice::SliceIndex,
 };
 
 /// a const-sized vector of elements, supports all math operations that T does on an
@@ -13,8 +15,9 @@ use core::{
 /// can be iterated over using [.into_iter()](#method.into_iter) on Vector or &Vector
 /// can be constructed from iterators using collect().
 ///
-/// is repr(align(16)) for simd
-#[repr(align(16))] // todo: choose alignment based on simd-width
+/// is repr(C, align(16)) for simd, the elements are always stored at the start of the Vector,
+/// followed by padding up to the alignment.
+#[repr(C, align(16))] // todo: choose alignment based on simd-width
 #[derive(Copy, Clone)]
 pub struct Vector<T, const N: usize> {
 	pub(crate) inner: [T; N],
@@ -31,6 +34,54 @@ pub type Matrix<T, const M: usize, const N: usize> = Vector<Vector<T, M>, N>;
 impl<T, const N: usize> Vector<T, N> {
 	pub(crate) fn uninit_inner() -> MaybeUninit<[T; N]> { MaybeUninit::uninit() }
 	pub fn ascend(self) -> Vector<Self, 1> { Vector { inner: [self] } }
+
+	/// usable in const items, matrices are built by nesting calls.
+	/// keep in mind that the inner Vectors of a Matrix are its columns:
+	///
+	///     use optimath::{Matrix, Vector};
+	///     // rotates by 90 degrees
+	///     const ROTATE: Matrix<i32, 2, 2> = Vector::from_array([
+	///         Vector::from_array([0, 1]),
+	///         Vector::from_array([-1, 0]),
+	///     ]);
+	///     let x = Vector::from([1, 0]);
+	///     assert_eq!(ROTATE.matrix_multiply(&x.ascend()), Vector::from([0, 1]).ascend());
+	pub const fn from_array(inner: [T; N]) -> Self { Vector { inner } }
+	pub fn into_array(self) -> [T; N] { self.inner }
+
+	pub fn as_slice(&self) -> &[T] { &self.inner }
+	pub fn as_mut_slice(&mut self) -> &mut [T] { &mut self.inner }
+
+	/// whether slice has the same layout as a slice of Vectors
+	fn fits_chunks(slice: &[T]) -> bool {
+		// a slice of Vectors only looks like a slice of elements if there is no padding
+		let packed = core::mem::size_of::<Self>() == N * core::mem::size_of::<T>();
+		let aligned = (slice.as_ptr() as usize).is_multiple_of(core::mem::align_of::<Self>());
+		packed && aligned && N != 0 && slice.len().is_multiple_of(N)
+	}
+
+	/// reinterprets a long slice as a slice of Vectors without copying.
+	///
+	/// returns None if the Vectors contain padding (like `Vector<f32, 3>` does due to the
+	/// alignment), if the slice is not aligned for Vectors or if its length is not a multiple of N.
+	/// see [VectorRef::chunks](crate::VectorRef::chunks) for an alternative that always works.
+	pub fn chunks_from_slice(slice: &[T]) -> Option<&[Self]> {
+		if !Self::fits_chunks(slice) {
+			return None;
+		}
+		// size and alignment have been checked, Vector is repr(C) so the elements are at the start
+		let ptr = slice.as_ptr() as *const Self;
+		Some(unsafe { core::slice::from_raw_parts(ptr, slice.len() / N) })
+	}
+
+	/// mutable version of [chunks_from_slice](#method.chunks_from_slice)
+	pub fn chunks_from_slice_mut(slice: &mut [T]) -> Option<&mut [Self]> {
+		if !Self::fits_chunks(slice) {
+			return None;
+		}
+		let ptr = slice.as_mut_ptr() as *mut Self;
+		Some(unsafe { core::slice::from_raw_parts_mut(ptr, slice.len() / N) })
+	}
 }
 
 /// now you might be asking: hey djugei, why isn't this function just implemented directly on
@@ -62,13 +113,68 @@ impl<T, const N: usize> Stupidity<T> for Vector<T, N> {
 	}
 }
 
-impl<T, const N: usize> Index<usize> for Vector<T, N> {
-	type Output = T;
-	fn index(&self, index: usize) -> &T { &self.inner[index] }
+impl<T, I: SliceIndex<[T]>, const N: usize> Index<I> for Vector<T, N> {
+	type Output = I::Output;
+	fn index(&self, index: I) -> &I::Output { &self.inner[index] }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> IndexMut<I> for Vector<T, N> {
+	fn index_mut(&mut self, index: I) -> &mut I::Output { &mut self.inner[index] }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+	fn from(inner: [T; N]) -> Self { Vector { inner } }
 }
 
-impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
-	fn index_mut(&mut self, index: usize) -> &mut T { &mut self.inner[index] }
+impl<T, const N: usize> From<Vector<T, N>> for [T; N] {
+	fn from(vector: Vector<T, N>) -> Self { vector.inner }
+}
+
+/// nested arrays are read as a list of columns, just like nested Vectors
+impl<T, const M: usize, const N: usize> From<[[T; M]; N]> for Matrix<T, M, N> {
+	fn from(columns: [[T; M]; N]) -> Self {
+		Vector {
+			inner: columns.map(Vector::from_array),
+		}
+	}
+}
+
+impl<T, const M: usize, const N: usize> From<Matrix<T, M, N>> for [[T; M]; N] {
+	fn from(matrix: Matrix<T, M, N>) -> Self { matrix.inner.map(Vector::into_array) }
+}
+
+impl<T, const N: usize> AsRef<[T]> for Vector<T, N> {
+	fn as_ref(&self) -> &[T] { &self.inner }
+}
+
+impl<T, const N: usize> AsMut<[T]> for Vector<T, N> {
+	fn as_mut(&mut self) -> &mut [T] { &mut self.inner }
+}
+
+impl<T, const N: usize> AsRef<[T; N]> for Vector<T, N> {
+	fn as_ref(&self) -> &[T; N] { &self.inner }
+}
+
+impl<T, const N: usize> AsMut<[T; N]> for Vector<T, N> {
+	fn as_mut(&mut self) -> &mut [T; N] { &mut self.inner }
+}
+
+impl<T, const N: usize> Borrow<[T]> for Vector<T, N> {
+	fn borrow(&self) -> &[T] { &self.inner }
+}
+
+impl<T, const N: usize> BorrowMut<[T]> for Vector<T, N> {
+	fn borrow_mut(&mut self) -> &mut [T] { &mut self.inner }
+}
+
+/// gives access to all the slice methods, the math operations on Vector take precedence though.
+impl<T, const N: usize> Deref for Vector<T, N> {
+	type Target = [T];
+	fn deref(&self) -> &[T] { &self.inner }
+}
+
+impl<T, const N: usize> DerefMut for Vector<T, N> {
+	fn deref_mut(&mut self) -> &mut [T] { &mut self.inner }
 }
 
 impl<T, const N: usize> FromIterator<T> for Vector<T, N> {
@@ -141,34 +247,40 @@ impl<T: PartialEq, const N: usize> PartialEq for Vector<T, N> {
 
 impl<T: Eq, const N: usize> Eq for Vector<T, N> {}
 
+/// lexicographic, like slices.
+///
+/// the inherent element-wise [lt](#method.lt), [le](#method.le), [gt](#method.gt), [ge](#method.ge)
+/// and [clamp](#method.clamp) take precedence in method calls, use the operators,
+/// `PartialOrd::lt(&a, &b)` or `Ord::clamp(a, min, max)` for the lexicographic ones. clamp only
+/// wins over Ord::clamp because both take self by value.
+impl<T: PartialOrd, const N: usize> PartialOrd for Vector<T, N> {
+	fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+		self.inner[..].partial_cmp(&other.inner[..])
+	}
+}
+
+impl<T: Ord, const N: usize> Ord for Vector<T, N> {
+	fn cmp(&self, other: &Self) -> core::cmp::Ordering { self.inner[..].cmp(&other.inner[..]) }
+}
+
+/// hashes like the equivalent slice
+impl<T: core::hash::Hash, const N: usize> core::hash::Hash for Vector<T, N> {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) { self.inner[..].hash(state) }
+}
+
 use core::fmt::Debug;
 impl<T: Debug, const N: usize> Debug for Vector<T, N> {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::result::Result<(), core::fmt::Error> {
 		f.write_str("Vector<")?;
 		Debug::fmt(&N, f)?;
 		f.write_str(">[")?;
-		for i in self {
+		for (n, i) in self.into_iter().enumerate() {
+			if n > 0 {
+				f.write_str(", ")?;
+			}
 			i.fmt(f)?;
-			f.write_str(", ")?;
 		}
 		f.write_str("]")?;
 		Ok(())
 	}
 }
-
-use core::fmt::Display;
-impl<T: Display + Debug, const N: usize> Display for Vector<T, N> {
-	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::result::Result<(), core::fmt::Error> {
-		if f.alternate() {
-			Debug::fmt(self, f)?;
-		} else {
-			f.write_str("Vector[\n")?;
-			for i in self {
-				Display::fmt(i, f)?;
-				f.write_str(",\n")?;
-			}
-			f.write_str("]")?;
-		}
-		Ok(())
-	}
-}
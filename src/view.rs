@@ -2,9 +2,14 @@
 //!
 //! basically move trough the data at different strides and offsets
 //! currently only transposed matrices and contained flipped vectors
+//! and borrowed slices that are treated as fixed size vectors
 //!
 //! the Index trait sucks hard
-use crate::{consts::ConstIterator, types::Matrix};
+use crate::{
+	consts::ConstIterator,
+	types::{Matrix, Vector},
+};
+use core::convert::TryFrom;
 
 #[derive(Debug)]
 pub struct TransposedMatrixView<'a, T, const M: usize, const N: usize> {
@@ -51,6 +56,100 @@ impl<'a, T, const M: usize, const N: usize> IntoIterator for VectorView<'a, T, M
 	fn into_iter(self) -> Self::IntoIter { self.into() }
 }
 
+/// a borrowed slice of runtime-checked length N, usable like a &Vector<T, N>.
+///
+/// unlike a &Vector this has no alignment requirements, so it can point anywhere into a slice.
+#[derive(Debug)]
+pub struct VectorRef<'a, T, const N: usize> {
+	pub(crate) inner: &'a [T; N],
+}
+
+impl<'a, T, const N: usize> Copy for VectorRef<'a, T, N> {}
+impl<'a, T, const N: usize> Clone for VectorRef<'a, T, N> {
+	fn clone(&self) -> Self { *self }
+}
+
+impl<'a, T, const N: usize> VectorRef<'a, T, N> {
+	pub fn as_slice(self) -> &'a [T] { self.inner }
+
+	/// splits a long slice into consecutive VectorRefs, leftover elements are skipped
+	pub fn chunks(slice: &'a [T]) -> impl Iterator<Item = VectorRef<'a, T, N>> {
+		slice.chunks_exact(N).map(|chunk| VectorRef {
+			inner: <&[T; N]>::try_from(chunk).unwrap(),
+		})
+	}
+}
+
+impl<'a, T: Clone, const N: usize> VectorRef<'a, T, N> {
+	pub fn materialize(self) -> Vector<T, N> { self.into_iter().cloned().collect() }
+}
+
+impl<'a, T, const N: usize> TryFrom<&'a [T]> for VectorRef<'a, T, N> {
+	type Error = core::array::TryFromSliceError;
+	fn try_from(slice: &'a [T]) -> Result<Self, Self::Error> {
+		Ok(VectorRef {
+			inner: <&[T; N]>::try_from(slice)?,
+		})
+	}
+}
+
+impl<'a, T, const N: usize> From<&'a Vector<T, N>> for VectorRef<'a, T, N> {
+	fn from(vector: &'a Vector<T, N>) -> Self { VectorRef { inner: &vector.inner } }
+}
+
+impl<'a, T, const N: usize> IntoIterator for VectorRef<'a, T, N> {
+	type Item = &'a T;
+	type IntoIter = ConstIterator<&'a T, Self, N>;
+
+	fn into_iter(self) -> Self::IntoIter { self.into() }
+}
+
+/// a mutably borrowed slice of runtime-checked length N, usable like a &mut Vector<T, N>.
+#[derive(Debug)]
+pub struct VectorMut<'a, T, const N: usize> {
+	pub(crate) inner: &'a mut [T; N],
+}
+
+impl<'a, T, const N: usize> VectorMut<'a, T, N> {
+	pub fn as_slice(&self) -> &[T] { self.inner }
+	pub fn as_mut_slice(&mut self) -> &mut [T] { self.inner }
+
+	/// splits a long slice into consecutive VectorMuts, leftover elements are skipped
+	pub fn chunks(slice: &'a mut [T]) -> impl Iterator<Item = VectorMut<'a, T, N>> {
+		slice.chunks_exact_mut(N).map(|chunk| VectorMut {
+			inner: <&mut [T; N]>::try_from(chunk).unwrap(),
+		})
+	}
+}
+
+impl<'a, T: Clone, const N: usize> VectorMut<'a, T, N> {
+	pub fn materialize(&self) -> Vector<T, N> { self.into_iter().cloned().collect() }
+}
+
+impl<'a, T, const N: usize> TryFrom<&'a mut [T]> for VectorMut<'a, T, N> {
+	type Error = core::array::TryFromSliceError;
+	fn try_from(slice: &'a mut [T]) -> Result<Self, Self::Error> {
+		Ok(VectorMut {
+			inner: <&mut [T; N]>::try_from(slice)?,
+		})
+	}
+}
+
+impl<'a, T, const N: usize> From<&'a mut Vector<T, N>> for VectorMut<'a, T, N> {
+	fn from(vector: &'a mut Vector<T, N>) -> Self {
+		VectorMut {
+			inner: &mut vector.inner,
+		}
+	}
+}
+
+impl<'a, 'b, T, const N: usize> IntoIterator for &'b VectorMut<'a, T, N> {
+	type Item = &'b T;
+	type IntoIter = ConstIterator<&'b T, Self, N>;
+
+	fn into_iter(self) -> Self::IntoIter { self.into() }
+}
+
 #[test]
 fn transpose_bounds() {
 	extern crate std;
@@ -63,3 +162,38 @@ fn transpose_bounds() {
 
 	assert_eq!(a, a2);
 }
+
+#[test]
+fn slice_views() {
+	use crate::Stupidity;
+	let mut data: [f32; 10] = [0., 1., 2., 3., 4., 5., 6., 7., 8., 9.];
+	let b: Vector<f32, 3> = Vector::build_with_fn(|i| i as f32);
+
+	// deliberately unaligned
+	let a = VectorRef::<f32, 3>::try_from(&data[1..4]).unwrap();
+	assert!(VectorRef::<f32, 3>::try_from(&data[1..5]).is_err());
+	let sum = a + &b;
+	assert_eq!(sum.as_slice(), &[1., 3., 5.]);
+	assert_eq!(&b + a, sum);
+	assert_eq!(a.materialize().as_slice(), &data[1..4]);
+
+	let chunks: usize = VectorRef::<f32, 3>::chunks(&data).map(|c| c.dot(&b) as usize).sum();
+	assert_eq!(chunks, 5 + 14 + 23);
+
+	for mut chunk in VectorMut::<f32, 3>::chunks(&mut data[1..]) {
+		chunk += &b;
+	}
+	assert_eq!(data, [0., 1., 3., 5., 4., 6., 8., 7., 9., 11.]);
+
+	let mut m = VectorMut::<f32, 3>::try_from(&mut data[..3]).unwrap();
+	m *= &b;
+	assert_eq!(m.materialize().as_slice(), &[0., 1., 6.]);
+
+	// Vector<f32, 3> contains padding, so it can not be chunked without copying
+	assert!(Vector::<f32, 3>::chunks_from_slice(&data[..9]).is_none());
+	let aligned: Vector<f32, 8> = Vector::build_with_fn(|i| i as f32);
+	let chunked = Vector::<f32, 4>::chunks_from_slice(aligned.as_slice()).unwrap();
+	assert_eq!(chunked.len(), 2);
+	assert_eq!(chunked[1].as_slice(), &[4., 5., 6., 7.]);
+	assert!(Vector::<f32, 4>::chunks_from_slice(&aligned.as_slice()[1..5]).is_none());
+}